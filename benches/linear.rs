@@ -0,0 +1,30 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+use csscolorparser::Color;
+
+fn bench_linear(c: &mut Criterion) {
+    let colors: Vec<Color> = (0..=255)
+        .map(|i| Color::from_rgba8(i, 255 - i, i / 2, 255))
+        .collect();
+
+    let mut group = c.benchmark_group("to_linear_rgba");
+    group.throughput(Throughput::Elements(colors.len() as u64));
+    group.bench_function("exact", |b| {
+        b.iter(|| {
+            for c in &colors {
+                black_box(black_box(c).to_linear_rgba());
+            }
+        })
+    });
+    group.bench_function("fast", |b| {
+        b.iter(|| {
+            for c in &colors {
+                black_box(black_box(c).to_linear_rgba_fast());
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_linear);
+criterion_main!(benches);
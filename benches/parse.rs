@@ -0,0 +1,81 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+use csscolorparser::parse;
+
+const HEX: [&str; 4] = ["#f00", "#ff0000", "#ff000080", "0x7654cd"];
+
+const NAMED: [&str; 4] = [
+    "red",
+    "rebeccapurple",
+    "LightGoldenrodYellow",
+    "transparent",
+];
+
+const RGB: [&str; 4] = [
+    "rgb(255,0,0)",
+    "rgb(255 0 0 / 50%)",
+    "rgba(100%, 50%, 0%, 0.5)",
+    "rgb(none 128 255)",
+];
+
+const HSL: [&str; 4] = [
+    "hsl(120,100%,50%)",
+    "hsl(120deg 100% 50% / 50%)",
+    "hsla(0.5turn, 50%, 25%, 0.8)",
+    "hsl(none 0% 50%)",
+];
+
+const OKLCH: [&str; 4] = [
+    "oklch(0.628 0.2577 29.23)",
+    "oklch(62.8% 0.2577 29.23deg / 50%)",
+    "oklch(0.452 0.3132 264.05)",
+    "oklch(1 0 none)",
+];
+
+const RELATIVE: [&str; 4] = [
+    "rgb(from #bad455 r g b)",
+    "hsl(from #ff0000 calc(h + 120) s l)",
+    "oklch(from #bad455 calc(clamp(0, l, 1)) c h)",
+    "rgb(from rgb(255 0 0) b g r / calc(alpha / 2))",
+];
+
+fn bench_group(c: &mut Criterion, name: &str, inputs: &[&str]) {
+    c.bench_function(name, |b| {
+        b.iter(|| {
+            for s in inputs {
+                let _ = black_box(parse(black_box(s)));
+            }
+        })
+    });
+}
+
+fn bench_parse(c: &mut Criterion) {
+    bench_group(c, "hex", &HEX);
+    #[cfg(feature = "named-colors")]
+    bench_group(c, "named", &NAMED);
+    bench_group(c, "rgb", &RGB);
+    bench_group(c, "hsl", &HSL);
+    bench_group(c, "oklch", &OKLCH);
+    bench_group(c, "relative", &RELATIVE);
+}
+
+fn bench_mixed(c: &mut Criterion) {
+    let mut inputs: Vec<&str> = [HEX, RGB, HSL, OKLCH, RELATIVE].concat();
+    if cfg!(feature = "named-colors") {
+        inputs.extend_from_slice(&NAMED);
+    }
+
+    let mut group = c.benchmark_group("mixed");
+    group.throughput(Throughput::Elements(inputs.len() as u64));
+    group.bench_function("parse", |b| {
+        b.iter(|| {
+            for s in &inputs {
+                let _ = black_box(parse(black_box(s)));
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse, bench_mixed);
+criterion_main!(benches);
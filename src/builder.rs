@@ -0,0 +1,82 @@
+use crate::Color;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Space {
+    Rgb([f32; 3]),
+    Hsl([f32; 3]),
+}
+
+/// Build a [`Color`] from its components step by step.
+///
+/// Only one color space is used: a later call to [`rgb`](#method.rgb) or [`hsl`](#method.hsl)
+/// replaces any earlier one. Without a color space the color is black, and the alpha defaults
+/// to 1.
+///
+/// # Examples
+/// ```
+/// use csscolorparser::ColorBuilder;
+///
+/// let c = ColorBuilder::new().hsl(120.0, 1.0, 0.5).alpha(0.5).build();
+///
+/// assert_eq!(c.to_rgba8(), [0, 255, 0, 128]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorBuilder {
+    space: Option<Space>,
+    alpha: f32,
+}
+
+impl ColorBuilder {
+    pub fn new() -> Self {
+        Self {
+            space: None,
+            alpha: 1.0,
+        }
+    }
+
+    /// Arguments:
+    ///
+    /// * `r`: Red value [0..1]
+    /// * `g`: Green value [0..1]
+    /// * `b`: Blue value [0..1]
+    #[must_use]
+    pub fn rgb(mut self, r: f32, g: f32, b: f32) -> Self {
+        self.space = Some(Space::Rgb([r, g, b]));
+        self
+    }
+
+    /// Arguments:
+    ///
+    /// * `h`: Hue angle [0..360]
+    /// * `s`: Saturation [0..1]
+    /// * `l`: Lightness [0..1]
+    #[must_use]
+    pub fn hsl(mut self, h: f32, s: f32, l: f32) -> Self {
+        self.space = Some(Space::Hsl([h, s, l]));
+        self
+    }
+
+    /// Arguments:
+    ///
+    /// * `a`: Alpha value [0..1]
+    #[must_use]
+    pub fn alpha(mut self, a: f32) -> Self {
+        self.alpha = a;
+        self
+    }
+
+    #[must_use]
+    pub fn build(&self) -> Color {
+        match self.space {
+            Some(Space::Rgb([r, g, b])) => Color::new(r, g, b, self.alpha),
+            Some(Space::Hsl([h, s, l])) => Color::from_hsla(h, s, l, self.alpha),
+            None => Color::new(0.0, 0.0, 0.0, self.alpha),
+        }
+    }
+}
+
+impl Default for ColorBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,7 +1,9 @@
+use std::cmp::Ordering;
 use std::convert::TryFrom;
-#[cfg(feature = "lab")]
 use std::f32::consts::{PI, TAU};
 use std::fmt;
+use std::iter::FromIterator;
+use std::ops::{Add, Mul, Sub};
 use std::str::FromStr;
 
 #[cfg(feature = "rust-rgb")]
@@ -10,17 +12,112 @@ use rgb::{RGB, RGBA};
 #[cfg(feature = "serde")]
 use serde::{de::Visitor, Deserialize, Deserializer, Serialize, Serializer};
 
-#[cfg(feature = "lab")]
-use lab::{LCh, Lab};
-
+use crate::lab;
 use crate::{parse, ParseColorError};
 
 #[cfg(feature = "named-colors")]
-use crate::parser::NAMED_COLORS;
+use crate::parser::{parse_named, NAMED_COLORS};
 
-#[cfg(feature = "lab")]
 const PI_3: f32 = PI * 3.0;
 
+// Chroma below which the hue is powerless (written as `none`), in Oklch and in CIE LCh.
+const OKLCH_POWERLESS_CHROMA: f32 = 5e-5;
+const LCH_POWERLESS_CHROMA: f32 = 5e-3;
+
+/// How the alpha value is written in CSS color strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlphaStyle {
+    /// Percentage, e.g. `rgb(255 0 0 / 50%)`
+    Percentage,
+    /// Decimal number, e.g. `rgb(255 0 0 / 0.5)`
+    Decimal,
+}
+
+// Not derived, `#[default]` on enum variants needs Rust 1.62.
+#[allow(clippy::derivable_impls)]
+impl Default for AlphaStyle {
+    fn default() -> Self {
+        Self::Percentage
+    }
+}
+
+/// When the alpha is included in hexadecimal color strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlphaMode {
+    /// Only if the color is not fully opaque
+    Auto,
+    /// Always, e.g. `#ff0000ff`
+    Always,
+    /// Never, the alpha is dropped
+    Never,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for AlphaMode {
+    fn default() -> Self {
+        Self::Auto
+    }
+}
+
+/// Color space for [`Color::interpolate_eased()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterpSpace {
+    /// sRGB, see [`Color::interpolate_rgb()`]
+    Rgb,
+    /// Linear RGB, see [`Color::interpolate_linear_rgb()`]
+    LinearRgb,
+    /// HSV, see [`Color::interpolate_hsv()`]
+    Hsv,
+    /// Oklab, see [`Color::interpolate_oklab()`]
+    Oklab,
+    /// CIE Lab, see [`Color::interpolate_lab()`]
+    Lab,
+    /// CIE LCh, see [`Color::interpolate_lch()`]
+    Lch,
+}
+
+/// How [`Color::to_srgb_gamut_with()`] maps a color outside the sRGB gamut into it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GamutMapMethod {
+    /// Clamp each RGB channel to [0..1]. Cheap, but can shift the hue and lightness noticeably.
+    Clip,
+    /// Reduce the Oklch chroma, keeping lightness and hue, until the color is in the gamut.
+    /// Lightness and hue are preserved exactly, at the cost of the most desaturated result.
+    OklchChromaReduction,
+    /// The [CSS Color 4 gamut mapping](https://www.w3.org/TR/css-color-4/#gamut-mapping)
+    /// algorithm: the Oklch chroma is reduced until clipping the result is not noticeable, then
+    /// the result is clipped. Keeps more chroma than `OklchChromaReduction`.
+    ClipChromaHybrid,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for GamutMapMethod {
+    fn default() -> Self {
+        Self::ClipChromaHybrid
+    }
+}
+
+/// Reference white of CIE XYZ values, see [`Color::from_xyz()`] and [`Color::to_xyz()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Illuminant {
+    /// D65, the reference white of sRGB, as used by CSS `color(xyz-d65 ...)`
+    D65,
+    /// D50, as used by CSS `color(xyz-d50 ...)` and `lab()`. Converted from and to D65 with the
+    /// Bradford chromatic adaptation.
+    D50,
+}
+
+/// Options for [`Color::to_css_hex_with()`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct HexOptions {
+    /// Use uppercase hex digits
+    pub uppercase: bool,
+    /// Use the short form (`#rgb` or `#rgba`) when it is lossless
+    pub short: bool,
+    /// When to include the alpha
+    pub alpha: AlphaMode,
+}
+
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 /// The color
 pub struct Color {
@@ -41,32 +138,94 @@ impl Color {
     /// * `g`: Green value [0..1]
     /// * `b`: Blue value [0..1]
     /// * `a`: Alpha value [0..1]
+    ///
+    /// The arguments are not checked. A `NaN` channel is accepted on purpose, it stands for a
+    /// missing (`none`) component, e.g. [to_css_rgb](#method.to_css_rgb) writes it as `none`. Use
+    /// [try_new](#method.try_new) to reject `NaN` and infinite values.
     pub const fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
         Self { r, g, b, a }
     }
 
+    /// Like [new](#method.new), but returns `None` if any of the arguments is `NaN` or infinite.
+    pub fn try_new(r: f32, g: f32, b: f32, a: f32) -> Option<Self> {
+        if r.is_finite() && g.is_finite() && b.is_finite() && a.is_finite() {
+            return Some(Self { r, g, b, a });
+        }
+        None
+    }
+
+    /// Returns: `[r, g, b, a]`, not clamped. Same as [to_array_raw](#method.to_array_raw).
+    ///
+    /// Prefer [to_array_raw](#method.to_array_raw) or [to_array_clamped](#method.to_array_clamped),
+    /// which make the handling of out of gamut values explicit.
     pub fn to_array(&self) -> [f32; 4] {
         [self.r, self.g, self.b, self.a]
     }
 
+    /// Returns: `[r, g, b, a]`, not clamped. The values may be outside [0..1] for colors outside
+    /// the sRGB gamut.
+    pub fn to_array_raw(&self) -> [f32; 4] {
+        [self.r, self.g, self.b, self.a]
+    }
+
+    /// Returns: `[r, g, b, a]`, clamped to [0..1].
+    pub fn to_array_clamped(&self) -> [f32; 4] {
+        self.clamp().to_array_raw()
+    }
+
+    /// Iterator over the components in the order `r`, `g`, `b`, `a`, not clamped.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let colors = [Color::new(1.0, 0.0, 0.0, 1.0), Color::new(0.0, 0.0, 1.0, 0.5)];
+    /// let buf = colors.iter().flat_map(Color::components).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(buf, [1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.5]);
+    /// ```
+    pub fn components(&self) -> impl Iterator<Item = f32> {
+        IntoIterator::into_iter(self.to_array())
+    }
+
+    /// Returns: `[r, g, b, a]` as `f64`, not clamped.
+    pub fn to_rgba_f64(&self) -> [f64; 4] {
+        [self.r as f64, self.g as f64, self.b as f64, self.a as f64]
+    }
+
     pub fn to_rgba8(&self) -> [u8; 4] {
         [
-            (self.r * 255.0 + 0.5) as u8,
-            (self.g * 255.0 + 0.5) as u8,
-            (self.b * 255.0 + 0.5) as u8,
-            (self.a * 255.0 + 0.5) as u8,
+            (self.r.clamp(0.0, 1.0) * 255.0 + 0.5) as u8,
+            (self.g.clamp(0.0, 1.0) * 255.0 + 0.5) as u8,
+            (self.b.clamp(0.0, 1.0) * 255.0 + 0.5) as u8,
+            (self.a.clamp(0.0, 1.0) * 255.0 + 0.5) as u8,
         ]
     }
 
     pub fn to_rgba16(&self) -> [u16; 4] {
         [
-            (self.r * 65535.0 + 0.5) as u16,
-            (self.g * 65535.0 + 0.5) as u16,
-            (self.b * 65535.0 + 0.5) as u16,
-            (self.a * 65535.0 + 0.5) as u16,
+            (self.r.clamp(0.0, 1.0) * 65535.0 + 0.5) as u16,
+            (self.g.clamp(0.0, 1.0) * 65535.0 + 0.5) as u16,
+            (self.b.clamp(0.0, 1.0) * 65535.0 + 0.5) as u16,
+            (self.a.clamp(0.0, 1.0) * 65535.0 + 0.5) as u16,
         ]
     }
 
+    /// Returns the color packed into a `u32` in ARGB byte order, `0xAARRGGBB`, with 8 bits per
+    /// channel, as used by some game engines and graphics APIs.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// assert_eq!(Color::from_rgba8(255, 0, 0, 128).to_u32_argb(), 0x80ff0000);
+    /// ```
+    pub fn to_u32_argb(&self) -> u32 {
+        let [r, g, b, a] = self.to_rgba8();
+        u32::from_be_bytes([a, r, g, b])
+    }
+
+    #[must_use]
     pub fn clamp(&self) -> Self {
         Self {
             r: self.r.clamp(0.0, 1.0),
@@ -76,6 +235,17 @@ impl Color {
         }
     }
 
+    /// Quantize the color to 8-bit precision, through [to_rgba8](#method.to_rgba8) and
+    /// [from_rgba8](#method.from_rgba8). The result is clamped.
+    ///
+    /// Colors parsed from the same 8-bit value then serialize to exactly the same string, in
+    /// any format, and rounding again is a no-op.
+    #[must_use]
+    pub fn round_to_rgba8(&self) -> Self {
+        let [r, g, b, a] = self.to_rgba8();
+        Self::from_rgba8(r, g, b, a)
+    }
+
     #[deprecated = "Use [new](#method.new) instead."]
     /// Arguments:
     ///
@@ -143,6 +313,45 @@ impl Color {
         }
     }
 
+    /// Create color from a `u32` in ARGB byte order, `0xAARRGGBB`, with 8 bits per channel.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// assert_eq!(Color::from_u32_argb(0x80ff0000).to_rgba8(), [255, 0, 0, 128]);
+    /// ```
+    pub fn from_u32_argb(argb: u32) -> Self {
+        let [a, r, g, b] = argb.to_be_bytes();
+        Self::from_rgba8(r, g, b, a)
+    }
+
+    /// Create color from `[r, g, b, a]` as `f64`. The values are not clamped.
+    pub fn from_rgba_f64(rgba: [f64; 4]) -> Self {
+        rgba.into()
+    }
+
+    /// Create a gray color, with `r`, `g` and `b` set to `value` [0..1]. The alpha is 1.
+    pub fn from_grayscale(value: f32) -> Self {
+        Self::new(value, value, value, 1.0)
+    }
+
+    /// Create a gray color, with `r`, `g` and `b` set to `value` [0..1], and the given alpha.
+    pub fn from_grayscale_alpha(value: f32, alpha: f32) -> Self {
+        Self::new(value, value, value, alpha)
+    }
+
+    /// Create a gray color, with `r`, `g` and `b` set to `value` [0..255]. The alpha is 255.
+    pub fn from_gray_u8(value: u8) -> Self {
+        Self::from_rgba8(value, value, value, 255)
+    }
+
+    /// Create a gray color, with `r`, `g` and `b` set to `value` [0..255], and the given
+    /// alpha [0..255].
+    pub fn from_gray_alpha_u8(value: u8, alpha: u8) -> Self {
+        Self::from_rgba8(value, value, value, alpha)
+    }
+
     #[deprecated = "Use [from_linear_rgba](#method.from_linear_rgba) instead."]
     /// Arguments:
     ///
@@ -306,39 +515,80 @@ impl Color {
         Self::from_oklaba(l, c * h.cos(), c * h.sin(), alpha)
     }
 
-    #[cfg(feature = "lab")]
+    /// Like [from_oklcha](#method.from_oklcha), but an out of gamut result is mapped into the
+    /// sRGB gamut using [to_srgb_gamut](#method.to_srgb_gamut).
+    ///
+    /// Arguments:
+    ///
+    /// * `l`: Perceived lightness
+    /// * `c`: Chroma
+    /// * `h`: Hue angle in radians
+    /// * `alpha`: Alpha [0..1]
+    pub fn from_oklcha_gamut(l: f32, c: f32, h: f32, alpha: f32) -> Self {
+        Self::from_oklcha(l, c, h, alpha).to_srgb_gamut()
+    }
+
     /// Arguments:
     ///
-    /// * `l`: Lightness
+    /// * `l`: Lightness [0..100]
     /// * `a`: Distance along the `a` axis
     /// * `b`: Distance along the `b` axis
     /// * `alpha`: Alpha [0..1]
+    ///
+    /// CIE Lab with the D50 reference white, as used by CSS `lab()`.
     pub fn from_laba(l: f32, a: f32, b: f32, alpha: f32) -> Self {
-        let [r, g, b] = Lab { l, a, b }.to_rgb_normalized();
-        Self::new(r, g, b, alpha)
+        let [r, g, b] = lab::lab_to_linear_rgb([l, a, b]);
+        Self::from_linear_rgba(r, g, b, alpha)
     }
 
-    #[cfg(feature = "lab")]
     #[deprecated = "Use [from_laba](#method.from_laba) instead."]
     pub fn from_lab(l: f32, a: f32, b: f32, alpha: f32) -> Self {
         Self::from_laba(l, a, b, alpha)
     }
 
-    #[cfg(feature = "lab")]
+    /// Create a color from CIE XYZ values with the given reference white. The alpha is 1.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::{Color, Illuminant};
+    ///
+    /// let c = Color::from_xyz([0.9643, 1.0, 0.8251], Illuminant::D50);
+    ///
+    /// assert_eq!(c.to_rgba8(), [255, 255, 255, 255]);
+    /// ```
+    pub fn from_xyz(xyz: [f32; 3], illuminant: Illuminant) -> Self {
+        let [r, g, b] = match illuminant {
+            Illuminant::D65 => lab::xyz_d65_to_linear_rgb(xyz),
+            Illuminant::D50 => lab::xyz_d50_to_linear_rgb(xyz),
+        };
+        Self::from_linear_rgba(r, g, b, 1.0)
+    }
+
+    /// Returns: `[x, y, z]`, CIE XYZ with the given reference white. The alpha is ignored.
+    pub fn to_xyz(&self, illuminant: Illuminant) -> [f32; 3] {
+        let [r, g, b, _] = self.to_linear_rgba();
+        match illuminant {
+            Illuminant::D65 => lab::linear_rgb_to_xyz_d65([r, g, b]),
+            Illuminant::D50 => lab::linear_rgb_to_xyz_d50([r, g, b]),
+        }
+    }
+
     /// Returns: `[l, a, b, alpha]`
+    ///
+    /// CIE Lab with the D50 reference white, as used by CSS `lab()`.
     pub fn to_laba(&self) -> [f32; 4] {
-        let Lab { l, a, b } = Lab::from_rgb_normalized(&[self.r, self.g, self.b]);
-        [l, a, b, self.a]
+        let [r, g, b, alpha] = self.to_linear_rgba();
+        let [l, a, b] = lab::linear_rgb_to_lab([r, g, b]);
+        [l, a, b, alpha]
     }
 
-    #[cfg(feature = "lab")]
     #[deprecated = "Use [to_laba](#method.to_laba) instead."]
     pub fn to_lab(&self) -> [f32; 4] {
         self.to_laba()
     }
 
-    #[cfg(feature = "lab")]
     /// Blend this color with the other one, in the Lab color-space. `t` in the range [0..1].
+    #[must_use]
     pub fn interpolate_lab(&self, other: &Color, t: f32) -> Self {
         let [l1, a1, b1, alpha1] = self.to_laba();
         let [l2, a2, b2, alpha2] = other.to_laba();
@@ -350,39 +600,116 @@ impl Color {
         )
     }
 
-    #[cfg(feature = "lab")]
     /// Arguments:
     ///
-    /// * `l`: Lightness
+    /// * `l`: Lightness [0..100]
     /// * `c`: Chroma
     /// * `h`: Hue angle in radians
     /// * `alpha`: Alpha [0..1]
     pub fn from_lcha(l: f32, c: f32, h: f32, alpha: f32) -> Self {
-        let [r, g, b] = LCh { l, c, h }.to_lab().to_rgb_normalized();
-        Self::new(r, g, b, alpha)
+        let [l, a, b] = lab::lch_to_lab([l, c, h]);
+        Self::from_laba(l, a, b, alpha)
     }
 
-    #[cfg(feature = "lab")]
     #[deprecated = "Use [from_lcha](#method.from_lcha) instead."]
     pub fn from_lch(l: f32, c: f32, h: f32, alpha: f32) -> Self {
         Self::from_lcha(l, c, h, alpha)
     }
 
-    #[cfg(feature = "lab")]
     /// Returns: `[l, c, h, alpha]`
+    ///
+    /// * `h`: Hue angle in radians [0..2π)
     pub fn to_lcha(&self) -> [f32; 4] {
-        let LCh { l, c, h } = LCh::from_lab(Lab::from_rgb_normalized(&[self.r, self.g, self.b]));
-        [l, c, h, self.a]
+        let [l, a, b, alpha] = self.to_laba();
+        let [l, c, h] = lab::lab_to_lch([l, a, b]);
+        [l, c, h, alpha]
     }
 
-    #[cfg(feature = "lab")]
     #[deprecated = "Use [to_lcha](#method.to_lcha) instead."]
     pub fn to_lch(&self) -> [f32; 4] {
         self.to_lcha()
     }
 
-    #[cfg(feature = "lab")]
+    /// HCL, as used by e.g. R and ggplot2, is CIE LCH with the components in a different order.
+    /// Same as `Color::from_lcha(l, c, h, alpha)`.
+    ///
+    /// Arguments:
+    ///
+    /// * `h`: Hue angle in radians
+    /// * `c`: Chroma
+    /// * `l`: Lightness [0..100]
+    /// * `alpha`: Alpha [0..1]
+    pub fn from_hcl(h: f32, c: f32, l: f32, alpha: f32) -> Self {
+        Self::from_lcha(l, c, h, alpha)
+    }
+
+    /// Returns: `[h, c, l, alpha]`, the [to_lcha](#method.to_lcha) components reordered.
+    ///
+    /// * `h`: Hue angle in radians [0..2π)
+    pub fn to_hcl(&self) -> [f32; 4] {
+        let [l, c, h, alpha] = self.to_lcha();
+        [h, c, l, alpha]
+    }
+
+    /// Color of a black body light source at the given temperature, e.g. 2700 K for a warm
+    /// incandescent bulb or 6500 K for daylight. Fully opaque.
+    ///
+    /// Uses Tanner Helland's approximation; `kelvin` is clamped to [1000..40000]. Around 6600 K
+    /// the color is white.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// assert_eq!(Color::from_temperature(6600.0).to_rgba8(), [255, 255, 255, 255]);
+    /// assert_eq!(Color::from_temperature(2700.0).to_hex_string(), "#ffa757");
+    /// ```
+    pub fn from_temperature(kelvin: f32) -> Self {
+        let t = kelvin.clamp(1000.0, 40000.0) / 100.0;
+        let (r, g) = if t <= 66.0 {
+            (255.0, 99.470_8 * t.ln() - 161.119_57)
+        } else {
+            (
+                329.698_73 * (t - 60.0).powf(-0.133_204_76),
+                288.122_17 * (t - 60.0).powf(-0.075_514_85),
+            )
+        };
+        let b = if t >= 66.0 {
+            255.0
+        } else if t <= 19.0 {
+            0.0
+        } else {
+            138.517_73 * (t - 10.0).ln() - 305.044_8
+        };
+        Self::new(
+            (r / 255.0).clamp(0.0, 1.0),
+            (g / 255.0).clamp(0.0, 1.0),
+            (b / 255.0).clamp(0.0, 1.0),
+            1.0,
+        )
+    }
+
+    /// Color of a dimmed light source, [from_temperature](#method.from_temperature) with the
+    /// RGB scaled by `brightness` [0..1], as for a dimmable light. `brightness` is clamped.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_kelvin_brightness(2700.0, 1.0);
+    /// assert_eq!(c, Color::from_temperature(2700.0));
+    ///
+    /// let c = Color::from_kelvin_brightness(2700.0, 0.0);
+    /// assert_eq!(c.to_rgba8(), [0, 0, 0, 255]);
+    /// ```
+    pub fn from_kelvin_brightness(kelvin: f32, brightness: f32) -> Self {
+        let c = Self::from_temperature(kelvin);
+        let k = brightness.clamp(0.0, 1.0);
+        Self::new(c.r * k, c.g * k, c.b * k, 1.0)
+    }
+
     /// Blend this color with the other one, in the LCH color-space. `t` in the range [0..1].
+    #[must_use]
     pub fn interpolate_lch(&self, other: &Color, t: f32) -> Self {
         let [l1, c1, h1, alpha1] = self.to_lcha();
         let [l2, c2, h2, alpha2] = other.to_lcha();
@@ -415,6 +742,37 @@ impl Color {
         parse(s.as_ref())
     }
 
+    /// Returns `true` if `s` is a valid color string. Same as
+    /// [`is_valid()`](fn.is_valid.html) and `parse(s).is_ok()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// assert!(Color::is_valid_css("#ff0000"));
+    /// assert!(!Color::is_valid_css("#ff000"));
+    /// ```
+    pub fn is_valid_css(s: &str) -> bool {
+        crate::is_valid(s)
+    }
+
+    #[cfg(feature = "named-colors")]
+    /// Create color from a CSS named color or `transparent` (case-insensitive).
+    ///
+    /// Unlike [`Color::from_html`], this only looks up the named color table, so hex
+    /// strings and color functions return `None`.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// assert_eq!(Color::from_named("red").unwrap().to_rgba8(), [255, 0, 0, 255]);
+    /// assert_eq!(Color::from_named("#f00"), None);
+    /// ```
+    pub fn from_named(name: &str) -> Option<Self> {
+        parse_named(name.trim())
+    }
+
     #[cfg(feature = "named-colors")]
     pub fn name(&self) -> Option<&'static str> {
         let rgb = &self.to_rgba8()[0..3];
@@ -440,10 +798,10 @@ impl Color {
     /// * Red, green, blue and alpha in the range [0..255]
     pub fn rgba_u8(&self) -> (u8, u8, u8, u8) {
         (
-            (self.r * 255.0).round() as u8,
-            (self.g * 255.0).round() as u8,
-            (self.b * 255.0).round() as u8,
-            (self.a * 255.0).round() as u8,
+            (self.r.clamp(0.0, 1.0) * 255.0).round() as u8,
+            (self.g.clamp(0.0, 1.0) * 255.0).round() as u8,
+            (self.b.clamp(0.0, 1.0) * 255.0).round() as u8,
+            (self.a.clamp(0.0, 1.0) * 255.0).round() as u8,
         )
     }
 
@@ -453,6 +811,9 @@ impl Color {
     /// * `s`: Saturation [0..1]
     /// * `v`: Value [0..1]
     /// * `a`: Alpha [0..1]
+    ///
+    /// The RGB values are not clamped, so the results may be outside these ranges for colors
+    /// outside the sRGB gamut. Use [`Color::clamp()`] first to get the in-gamut values.
     pub fn to_hsva(&self) -> [f32; 4] {
         let (h, s, v) = rgb_to_hsv(self.r, self.g, self.b);
         [h, s, v, self.a]
@@ -464,6 +825,9 @@ impl Color {
     /// * `s`: Saturation [0..1]
     /// * `l`: Lightness [0..1]
     /// * `a`: Alpha [0..1]
+    ///
+    /// The RGB values are not clamped, so the results may be outside these ranges for colors
+    /// outside the sRGB gamut. Use [`Color::clamp()`] first to get the in-gamut values.
     pub fn to_hsla(&self) -> [f32; 4] {
         let (h, s, l) = rgb_to_hsl(self.r, self.g, self.b);
         [h, s, l, self.a]
@@ -480,6 +844,24 @@ impl Color {
         [h, w, b, self.a]
     }
 
+    /// Returns: `(h, s, v, a)`. Same as [to_hsva](#method.to_hsva), as a tuple.
+    pub fn to_hsv_tuple(&self) -> (f32, f32, f32, f32) {
+        let [h, s, v, a] = self.to_hsva();
+        (h, s, v, a)
+    }
+
+    /// Returns: `(h, s, l, a)`. Same as [to_hsla](#method.to_hsla), as a tuple.
+    pub fn to_hsl_tuple(&self) -> (f32, f32, f32, f32) {
+        let [h, s, l, a] = self.to_hsla();
+        (h, s, l, a)
+    }
+
+    /// Returns: `(h, w, b, a)`. Same as [to_hwba](#method.to_hwba), as a tuple.
+    pub fn to_hwb_tuple(&self) -> (f32, f32, f32, f32) {
+        let [h, w, b, a] = self.to_hwba();
+        (h, w, b, a)
+    }
+
     /// Returns: `[r, g, b, a]`
     ///
     /// * Red, green, blue and alpha in the range [0..1]
@@ -498,16 +880,39 @@ impl Color {
         ]
     }
 
+    /// Like [to_linear_rgba](#method.to_linear_rgba), but using a cubic polynomial approximation
+    /// of the sRGB transfer function instead of `powf`, which is faster for bulk conversions.
+    ///
+    /// The maximum absolute error is 0.0017 for values in the range [0..1], at most 1 off after
+    /// rounding to 8 bits. Values outside [0..1] are less accurate.
+    ///
+    /// Returns: `[r, g, b, a]`
+    ///
+    /// * Red, green, blue and alpha in the range [0..1]
+    #[cfg(feature = "fast-linear")]
+    pub fn to_linear_rgba_fast(&self) -> [f32; 4] {
+        #[allow(clippy::excessive_precision)]
+        fn to_linear(x: f32) -> f32 {
+            x * (x * (x * 0.305306011 + 0.682171111) + 0.012522878)
+        }
+        [
+            to_linear(self.r),
+            to_linear(self.g),
+            to_linear(self.b),
+            self.a,
+        ]
+    }
+
     /// Returns: `[r, g, b, a]`
     ///
     /// * Red, green, blue and alpha in the range [0..255]
     pub fn to_linear_rgba_u8(&self) -> [u8; 4] {
         let [r, g, b, a] = self.to_linear_rgba();
         [
-            (r * 255.0).round() as u8,
-            (g * 255.0).round() as u8,
-            (b * 255.0).round() as u8,
-            (a * 255.0).round() as u8,
+            (r.clamp(0.0, 1.0) * 255.0).round() as u8,
+            (g.clamp(0.0, 1.0) * 255.0).round() as u8,
+            (b.clamp(0.0, 1.0) * 255.0).round() as u8,
+            (a.clamp(0.0, 1.0) * 255.0).round() as u8,
         ]
     }
 
@@ -524,7 +929,52 @@ impl Color {
         [l, a, b, self.a]
     }
 
+    /// Multiply the red, green and blue values by `factor`. Alpha is untouched.
+    ///
+    /// This operates on the gamma-encoded sRGB values, use
+    /// [map_linear_rgb](#method.map_linear_rgb) for physically based adjustments. The result is
+    /// not clamped.
+    #[must_use]
+    pub fn scale_rgb(&self, factor: f32) -> Self {
+        Self::new(self.r * factor, self.g * factor, self.b * factor, self.a)
+    }
+
+    /// Apply `f` to the red, green and blue values in the linear RGB color-space. Alpha is
+    /// preserved.
+    ///
+    /// # Examples
+    ///
+    /// Linear exposure adjustment.
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_linear_rgba(0.1, 0.2, 0.4, 1.0);
+    /// let x = c.map_linear_rgb(|[r, g, b]| [r * 2.0, g * 2.0, b * 2.0]);
+    ///
+    /// assert_eq!(x.to_rgba8(), Color::from_linear_rgba(0.2, 0.4, 0.8, 1.0).to_rgba8());
+    /// ```
+    #[must_use]
+    pub fn map_linear_rgb<F: Fn([f32; 3]) -> [f32; 3]>(&self, f: F) -> Self {
+        let [r, g, b, a] = self.to_linear_rgba();
+        let [r, g, b] = f([r, g, b]);
+        Self::from_linear_rgba(r, g, b, a)
+    }
+
+    /// Returns: `[l, c, h, alpha]`
+    ///
+    /// * `l`: Perceived lightness
+    /// * `c`: Chroma
+    /// * `h`: Hue angle in radians [0..2π)
+    /// * `alpha`: Alpha [0..1]
+    pub fn to_oklcha(&self) -> [f32; 4] {
+        let [l, a, b, alpha] = self.to_oklaba();
+        let c = (a * a + b * b).sqrt();
+        let h = b.atan2(a).rem_euclid(TAU);
+        [l, c, h, alpha]
+    }
+
     /// Get the RGB hexadecimal color string.
+    #[must_use]
     pub fn to_hex_string(&self) -> String {
         let [r, g, b, a] = self.to_rgba8();
 
@@ -536,6 +986,7 @@ impl Color {
     }
 
     /// Get the CSS `rgb()` format string.
+    #[must_use]
     pub fn to_rgb_string(&self) -> String {
         let [r, g, b, _] = self.to_rgba8();
 
@@ -546,43 +997,575 @@ impl Color {
         format!("rgb({},{},{})", r, g, b)
     }
 
-    /// Blend this color with the other one, in the RGB color-space. `t` in the range [0..1].
-    pub fn interpolate_rgb(&self, other: &Color, t: f32) -> Self {
-        Self {
-            r: self.r + t * (other.r - self.r),
-            g: self.g + t * (other.g - self.g),
-            b: self.b + t * (other.b - self.b),
-            a: self.a + t * (other.a - self.a),
-        }
+    /// Get the hexadecimal color string, e.g. `#ff0000` or `#ff000080`. Same as
+    /// [to_hex_string](#method.to_hex_string).
+    #[must_use]
+    pub fn to_css_hex(&self) -> String {
+        self.to_css_hex_with(HexOptions::default())
     }
 
-    /// Blend this color with the other one, in the linear RGB color-space. `t` in the range [0..1].
-    pub fn interpolate_linear_rgb(&self, other: &Color, t: f32) -> Self {
-        let [r1, g1, b1, a1] = self.to_linear_rgba();
-        let [r2, g2, b2, a2] = other.to_linear_rgba();
-        Self::from_linear_rgba(
-            r1 + t * (r2 - r1),
-            g1 + t * (g2 - g1),
-            b1 + t * (b2 - b1),
-            a1 + t * (a2 - a1),
-        )
+    /// Get the hexadecimal color string with the given options.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::{AlphaMode, Color, HexOptions};
+    ///
+    /// let c = Color::from_rgba8(255, 170, 0, 255);
+    ///
+    /// let opts = HexOptions {
+    ///     uppercase: true,
+    ///     short: true,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(c.to_css_hex_with(opts), "#FA0");
+    ///
+    /// let opts = HexOptions {
+    ///     alpha: AlphaMode::Always,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(c.to_css_hex_with(opts), "#ffaa00ff");
+    /// ```
+    #[must_use]
+    pub fn to_css_hex_with(&self, opts: HexOptions) -> String {
+        let [r, g, b, a] = self.to_rgba8();
+        let mut values = vec![r, g, b];
+        match opts.alpha {
+            AlphaMode::Auto if a < 255 => values.push(a),
+            AlphaMode::Always => values.push(a),
+            _ => {}
+        }
+        let short = opts.short && values.iter().all(|v| v >> 4 == v & 0xf);
+        let mut s = String::from("#");
+        for v in values {
+            let hex = if short {
+                format!("{:x}", v & 0xf)
+            } else {
+                format!("{:02x}", v)
+            };
+            s.push_str(&hex);
+        }
+        if opts.uppercase {
+            s.make_ascii_uppercase();
+        }
+        s
     }
 
-    /// Blend this color with the other one, in the HSV color-space. `t` in the range [0..1].
-    pub fn interpolate_hsv(&self, other: &Color, t: f32) -> Self {
-        let [h1, s1, v1, a1] = self.to_hsva();
-        let [h2, s2, v2, a2] = other.to_hsva();
-        Self::from_hsva(
-            interp_angle(h1, h2, t),
-            s1 + t * (s2 - s1),
-            v1 + t * (v2 - v1),
-            a1 + t * (a2 - a1),
-        )
+    /// Get the hexadecimal color string in ARGB order, with the alpha first: `#aarrggbb`.
+    ///
+    /// This is **not** the CSS order (see [to_css_hex](#method.to_css_hex)). It is used by
+    /// Android and some game engines, and can be parsed with
+    /// [`parse_argb_hex()`](fn.parse_argb_hex.html).
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_rgba8(255, 0, 0, 128);
+    ///
+    /// assert_eq!(c.to_argb_hex(), "#80ff0000");
+    /// assert_eq!(c.to_css_hex(), "#ff000080");
+    /// ```
+    #[must_use]
+    pub fn to_argb_hex(&self) -> String {
+        let [r, g, b, a] = self.to_rgba8();
+        format!("#{:02x}{:02x}{:02x}{:02x}", a, r, g, b)
     }
 
-    /// Blend this color with the other one, in the [Oklab](https://bottosson.github.io/posts/oklab/) color-space. `t` in the range [0..1].
-    pub fn interpolate_oklab(&self, other: &Color, t: f32) -> Self {
-        let [l1, a1, b1, alpha1] = self.to_oklaba();
+    /// Get a CSS string that represents this color faithfully, also outside the sRGB gamut.
+    ///
+    /// If the RGB channels are in the range [0..1] (with a small tolerance for rounding errors),
+    /// the [hexadecimal](#method.to_hex_string) string is used. Otherwise the hex string would
+    /// clamp the color, so [`oklch()`](#method.to_css_oklch) is used instead, which can represent
+    /// any color.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// assert_eq!(Color::new(1.0, 0.0, 0.0, 1.0).to_css_best(), "#ff0000");
+    /// assert_eq!(
+    ///     Color::from_oklcha(0.7, 0.4, 2.5, 1.0).to_css_best(),
+    ///     "oklch(0.7 0.4 143.24)"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn to_css_best(&self) -> String {
+        if self.is_in_srgb_gamut() {
+            self.to_hex_string()
+        } else {
+            self.to_css_oklch()
+        }
+    }
+
+    /// Get the shortest CSS string for this color at 8-bit precision, for minifiers.
+    ///
+    /// The candidates are the hexadecimal forms (`#rgb` or `#rgba` when lossless, otherwise
+    /// `#rrggbb` or `#rrggbbaa`) and, for opaque colors, the [named colors](#method.name). A name
+    /// is used only when it is strictly shorter than the hex string; among several names for the
+    /// same color the shortest, then alphabetically first, is used. `rgb()` is never shorter
+    /// than the hex forms, so it is not considered.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// assert_eq!(Color::from_rgba8(255, 255, 0, 255).to_css_minimal(), "#ff0");
+    /// assert_eq!(Color::from_rgba8(0, 0, 255, 128).to_css_minimal(), "#0000ff80");
+    /// # #[cfg(feature = "named-colors")]
+    /// assert_eq!(Color::from_rgba8(255, 0, 0, 255).to_css_minimal(), "red");
+    /// ```
+    #[must_use]
+    pub fn to_css_minimal(&self) -> String {
+        let hex = self.to_css_hex_with(HexOptions {
+            short: true,
+            ..Default::default()
+        });
+
+        #[cfg(feature = "named-colors")]
+        {
+            let [r, g, b, a] = self.to_rgba8();
+            let name = NAMED_COLORS
+                .entries()
+                .filter(|(_, &v)| a == 255 && v == [r, g, b])
+                .map(|(&k, _)| k)
+                .min_by_key(|k| (k.len(), *k));
+            if let Some(name) = name.filter(|k| k.len() < hex.len()) {
+                return name.to_string();
+            }
+        }
+
+        hex
+    }
+
+    /// Get the CSS `rgb()` format string, using the modern space-separated syntax.
+    /// A `NaN` channel is written as `none`.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// assert_eq!(Color::new(1.0, 0.0, 0.0, 1.0).to_css_rgb(), "rgb(255 0 0)");
+    /// assert_eq!(Color::new(1.0, 0.0, 0.0, 0.5).to_css_rgb(), "rgb(255 0 0 / 50%)");
+    /// ```
+    #[must_use]
+    pub fn to_css_rgb(&self) -> String {
+        self.to_css_rgb_with(AlphaStyle::Percentage)
+    }
+
+    /// Get the CSS `rgb()` format string, with the alpha written in the given style.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::{AlphaStyle, Color};
+    ///
+    /// let c = Color::new(1.0, 0.0, 0.0, 0.5);
+    ///
+    /// assert_eq!(c.to_css_rgb_with(AlphaStyle::Percentage), "rgb(255 0 0 / 50%)");
+    /// assert_eq!(c.to_css_rgb_with(AlphaStyle::Decimal), "rgb(255 0 0 / 0.5)");
+    /// ```
+    #[must_use]
+    pub fn to_css_rgb_with(&self, alpha_style: AlphaStyle) -> String {
+        let ch = |t: f32| {
+            if t.is_nan() {
+                return "none".to_string();
+            }
+            ((t.clamp(0.0, 1.0) * 255.0 + 0.5) as u8).to_string()
+        };
+        format!(
+            "rgb({} {} {}{})",
+            ch(self.r),
+            ch(self.g),
+            ch(self.b),
+            fmt_alpha_with(self.a, alpha_style)
+        )
+    }
+
+    /// Get the CSS `rgb()` format string with the channels not clamped or rounded to integers,
+    /// written with up to 2 decimal places. Useful to see whether a color is outside the sRGB
+    /// gamut, where channels are below 0 or above 255.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::new(1.2, 0.5, -0.1, 1.0);
+    ///
+    /// assert_eq!(c.to_css_rgb(), "rgb(255 128 0)");
+    /// assert_eq!(c.to_css_rgb_raw(), "rgb(306 127.5 -25.5)");
+    /// ```
+    #[must_use]
+    pub fn to_css_rgb_raw(&self) -> String {
+        let ch = |t: f32| {
+            if t.is_nan() {
+                return "none".to_string();
+            }
+            fmt_float(t * 255.0, 2)
+        };
+        format!(
+            "rgb({} {} {}{})",
+            ch(self.r),
+            ch(self.g),
+            ch(self.b),
+            fmt_alpha(self.a)
+        )
+    }
+
+    /// Get the CSS `hsl()` format string. The hue is written as `none` for achromatic colors.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// assert_eq!(Color::new(1.0, 0.0, 0.0, 1.0).to_css_hsl(), "hsl(0 100% 50%)");
+    /// assert_eq!(Color::new(0.5, 0.5, 0.5, 1.0).to_css_hsl(), "hsl(none 0% 50%)");
+    /// ```
+    #[must_use]
+    pub fn to_css_hsl(&self) -> String {
+        let [h, s, l, alpha] = self.to_hsla();
+        format!(
+            "hsl({} {}% {}%{})",
+            fmt_hue(h, s == 0.0),
+            fmt_float(s * 100.0, 2),
+            fmt_float(l * 100.0, 2),
+            fmt_alpha(alpha)
+        )
+    }
+
+    /// Get the `hsv()` format string. The hue is written as `none` for achromatic colors.
+    ///
+    /// Note that `hsv()` is not in the CSS standard, it is only understood by this crate (and
+    /// some other tools).
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// assert_eq!(Color::new(0.0, 1.0, 0.0, 1.0).to_css_hsv(), "hsv(120 100% 100%)");
+    /// assert_eq!(Color::new(0.5, 0.5, 0.5, 0.5).to_css_hsv(), "hsv(none 0% 50% / 50%)");
+    /// ```
+    #[must_use]
+    pub fn to_css_hsv(&self) -> String {
+        let [h, s, v, alpha] = self.to_hsva();
+        format!(
+            "hsv({} {}% {}%{})",
+            fmt_hue(h, s == 0.0),
+            fmt_float(s * 100.0, 2),
+            fmt_float(v * 100.0, 2),
+            fmt_alpha(alpha)
+        )
+    }
+
+    /// Get the CSS `hwb()` format string. Whiteness and blackness are rounded to integer
+    /// percentages, use [to_css_hwb_prec](#method.to_css_hwb_prec) for more precision.
+    #[must_use]
+    pub fn to_css_hwb(&self) -> String {
+        self.to_css_hwb_prec(0)
+    }
+
+    /// Get the CSS `hwb()` format string with `precision` decimal places for the whiteness and
+    /// blackness. The hue always keeps at least 2 decimal places.
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let c = csscolorparser::parse("hwb(290 30.5% 10.2%)")?;
+    ///
+    /// assert_eq!(c.to_css_hwb(), "hwb(290 30% 10%)");
+    /// assert_eq!(c.to_css_hwb_prec(1), "hwb(290 30.5% 10.2%)");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn to_css_hwb_prec(&self, precision: usize) -> String {
+        let [h, w, b, alpha] = self.to_hwba();
+        format!(
+            "hwb({} {}% {}%{})",
+            fmt_float(h, precision.max(2)),
+            fmt_float(w * 100.0, precision),
+            fmt_float(b * 100.0, precision),
+            fmt_alpha(alpha)
+        )
+    }
+
+    /// Get the CSS `oklab()` format string.
+    #[must_use]
+    pub fn to_css_oklab(&self) -> String {
+        self.to_css_oklab_snapped(0.0)
+    }
+
+    /// Get the CSS `oklab()` format string, with the `a` and `b` values whose magnitude is below
+    /// `epsilon` written as `0`. Useful to get clean output for (almost) gray colors.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_rgba8(128, 128, 129, 255);
+    ///
+    /// assert_eq!(c.to_css_oklab(), "oklab(0.6002 0.0004 -0.0014)");
+    /// assert_eq!(c.to_css_oklab_snapped(0.005), "oklab(0.6002 0 0)");
+    /// ```
+    #[must_use]
+    pub fn to_css_oklab_snapped(&self, epsilon: f32) -> String {
+        let [l, a, b, alpha] = self.to_oklaba();
+        let snap = |t: f32| if t.abs() < epsilon { 0.0 } else { t };
+        format!(
+            "oklab({} {} {}{})",
+            fmt_float(l, 4),
+            fmt_float(snap(a), 4),
+            fmt_float(snap(b), 4),
+            fmt_alpha(alpha)
+        )
+    }
+
+    /// Get the CSS `oklch()` format string. The hue is written as `none` for achromatic colors.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// assert_eq!(Color::new(1.0, 1.0, 1.0, 1.0).to_css_oklch(), "oklch(1 0 none)");
+    /// ```
+    #[must_use]
+    pub fn to_css_oklch(&self) -> String {
+        let [l, c, h, alpha] = self.to_oklcha();
+        format!(
+            "oklch({} {} {}{})",
+            fmt_float(l, 4),
+            fmt_float(c, 4),
+            fmt_hue(h.to_degrees(), c < OKLCH_POWERLESS_CHROMA),
+            fmt_alpha(alpha)
+        )
+    }
+
+    /// Get the CSS `oklch()` format string, with the lightness and chroma written as
+    /// percentages. For the chroma, `100%` is `0.4`.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::new(1.0, 0.0, 0.0, 1.0);
+    ///
+    /// assert_eq!(c.to_css_oklch(), "oklch(0.628 0.2577 29.23)");
+    /// assert_eq!(c.to_css_oklch_percent(), "oklch(62.8% 64.42% 29.23)");
+    /// ```
+    #[must_use]
+    pub fn to_css_oklch_percent(&self) -> String {
+        let [l, c, h, alpha] = self.to_oklcha();
+        format!(
+            "oklch({}% {}% {}{})",
+            fmt_float(l * 100.0, 2),
+            fmt_float(c / 0.4 * 100.0, 2),
+            fmt_hue(h.to_degrees(), c < OKLCH_POWERLESS_CHROMA),
+            fmt_alpha(alpha)
+        )
+    }
+
+    /// Get the CSS `lab()` format string.
+    #[must_use]
+    pub fn to_css_lab(&self) -> String {
+        let [l, a, b, alpha] = self.to_laba();
+        format!(
+            "lab({} {} {}{})",
+            fmt_float(l, 2),
+            fmt_float(a, 2),
+            fmt_float(b, 2),
+            fmt_alpha(alpha)
+        )
+    }
+
+    /// Get the CSS `lab()` format string, with the lightness written as a percentage.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::new(1.0, 0.0, 0.0, 1.0);
+    ///
+    /// assert_eq!(c.to_css_lab(), "lab(54.29 80.8 69.89)");
+    /// assert_eq!(c.to_css_lab_percent(), "lab(54.29% 80.8 69.89)");
+    /// ```
+    #[must_use]
+    pub fn to_css_lab_percent(&self) -> String {
+        let [l, a, b, alpha] = self.to_laba();
+        format!(
+            "lab({}% {} {}{})",
+            fmt_float(l, 2),
+            fmt_float(a, 2),
+            fmt_float(b, 2),
+            fmt_alpha(alpha)
+        )
+    }
+
+    /// Get the CSS `lch()` format string. The hue is written as `none` for achromatic colors.
+    #[must_use]
+    pub fn to_css_lch(&self) -> String {
+        let [l, c, h, alpha] = self.to_lcha();
+        format!(
+            "lch({} {} {}{})",
+            fmt_float(l, 2),
+            fmt_float(c, 2),
+            fmt_hue(h.to_degrees(), c < LCH_POWERLESS_CHROMA),
+            fmt_alpha(alpha)
+        )
+    }
+
+    /// Blend this color with the other one, in the RGB color-space. `t` in the range [0..1].
+    #[must_use]
+    pub fn interpolate_rgb(&self, other: &Color, t: f32) -> Self {
+        Self {
+            r: self.r + t * (other.r - self.r),
+            g: self.g + t * (other.g - self.g),
+            b: self.b + t * (other.b - self.b),
+            a: self.a + t * (other.a - self.a),
+        }
+    }
+
+    /// Blend this color with the other one, in the linear RGB color-space. `t` in the range [0..1].
+    #[must_use]
+    pub fn interpolate_linear_rgb(&self, other: &Color, t: f32) -> Self {
+        let [r1, g1, b1, a1] = self.to_linear_rgba();
+        let [r2, g2, b2, a2] = other.to_linear_rgba();
+        Self::from_linear_rgba(
+            r1 + t * (r2 - r1),
+            g1 + t * (g2 - g1),
+            b1 + t * (b2 - b1),
+            a1 + t * (a2 - a1),
+        )
+    }
+
+    /// Blend this color with the other one, in the linear RGB color-space with premultiplied
+    /// alpha. `t` in the range [0..1].
+    ///
+    /// This avoids the darkening of interpolating in gamma-encoded sRGB, and the color of a fully
+    /// transparent endpoint bleeding into the result.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let red = Color::new(1.0, 0.0, 0.0, 1.0);
+    /// let transparent = Color::new(0.0, 0.0, 0.0, 0.0);
+    /// let c = red.interpolate_premultiplied_linear(&transparent, 0.5);
+    ///
+    /// assert_eq!(c.to_rgba8(), [255, 0, 0, 128]);
+    /// ```
+    #[must_use]
+    pub fn interpolate_premultiplied_linear(&self, other: &Color, t: f32) -> Self {
+        let [r1, g1, b1, a1] = self.to_linear_rgba();
+        let [r2, g2, b2, a2] = other.to_linear_rgba();
+        let a = a1 + t * (a2 - a1);
+        if a <= 0.0 {
+            return Self::new(0.0, 0.0, 0.0, 0.0);
+        }
+        let mix = |c1: f32, c2: f32| (c1 * a1 + t * (c2 * a2 - c1 * a1)) / a;
+        Self::from_linear_rgba(mix(r1, r2), mix(g1, g2), mix(b1, b2), a)
+    }
+
+    /// Blend this color with the other one, in the RGB color-space, weighting the RGB of each
+    /// color by its alpha. `t` in the range [0..1].
+    ///
+    /// This is the premultiplied alpha interpolation CSS uses for gradients: a more opaque color
+    /// contributes more to the RGB of the result, and the color of a fully transparent endpoint
+    /// does not bleed in. [interpolate_rgb](#method.interpolate_rgb) instead blends the RGB as
+    /// if both colors were opaque. Unlike [over](#method.over), which composites one color on
+    /// top of the other, the alpha is interpolated linearly.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let red = Color::new(1.0, 0.0, 0.0, 1.0);
+    /// let blue = Color::new(0.0, 0.0, 1.0, 0.25);
+    ///
+    /// assert_eq!(red.interpolate_rgb(&blue, 0.5).to_rgba8(), [128, 0, 128, 159]);
+    /// assert_eq!(red.blend_with_alpha(&blue, 0.5).to_rgba8(), [204, 0, 51, 159]);
+    /// ```
+    #[must_use]
+    pub fn blend_with_alpha(&self, other: &Color, t: f32) -> Self {
+        let a = self.a + t * (other.a - self.a);
+        if a <= 0.0 {
+            return Self::new(0.0, 0.0, 0.0, 0.0);
+        }
+        let mix = |c1: f32, c2: f32| (c1 * self.a + t * (c2 * other.a - c1 * self.a)) / a;
+        Self::new(
+            mix(self.r, other.r),
+            mix(self.g, other.g),
+            mix(self.b, other.b),
+            a,
+        )
+    }
+
+    /// Blend this color with the other one in the RGB color-space, then correct the
+    /// [luminance](#method.luminance) of the result to the interpolated luminance of the two
+    /// colors. `t` in the range [0..1].
+    ///
+    /// Plain RGB interpolation between colors of different hue, e.g. blue and yellow, dips in
+    /// brightness in the middle. This is a heuristic to avoid that in color ramps: the linear
+    /// RGB of the blend is scaled to the target luminance, keeping its chromaticity, and if that
+    /// would leave the sRGB gamut it is mixed with white instead. The colors are clamped into
+    /// the sRGB gamut first.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let blue = Color::new(0.0, 0.0, 1.0, 1.0);
+    /// let yellow = Color::new(1.0, 1.0, 0.0, 1.0);
+    /// let c = blue.interpolate_rgb_luma_preserving(&yellow, 0.5);
+    ///
+    /// assert!((c.luminance() - 0.5).abs() < 1e-3);
+    /// assert!(blue.interpolate_rgb(&yellow, 0.5).luminance() < 0.25);
+    /// ```
+    #[must_use]
+    pub fn interpolate_rgb_luma_preserving(&self, other: &Color, t: f32) -> Self {
+        let c1 = self.clamp();
+        let c2 = other.clamp();
+        let target = c1.luminance() + t * (c2.luminance() - c1.luminance());
+
+        let [r, g, b, a] = c1.interpolate_rgb(&c2, t).to_linear_rgba();
+        let mut rgb = [r, g, b];
+        let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+        let max = r.max(g).max(b);
+
+        if y <= 0.0 {
+            rgb = [target; 3];
+        } else if max * target / y <= 1.0 {
+            rgb = rgb.map(|v| v * target / y);
+        } else {
+            // Scale up to the gamut boundary, then mix with white for the remaining luminance.
+            rgb = rgb.map(|v| v / max);
+            let y = y / max;
+            let k = if y < 1.0 {
+                (target - y) / (1.0 - y)
+            } else {
+                0.0
+            };
+            rgb = rgb.map(|v| v + k * (1.0 - v));
+        }
+
+        Self::from_linear_rgba(rgb[0], rgb[1], rgb[2], a)
+    }
+
+    /// Blend this color with the other one, in the HSV color-space. `t` in the range [0..1].
+    #[must_use]
+    pub fn interpolate_hsv(&self, other: &Color, t: f32) -> Self {
+        let [h1, s1, v1, a1] = self.to_hsva();
+        let [h2, s2, v2, a2] = other.to_hsva();
+        Self::from_hsva(
+            interp_angle(h1, h2, t),
+            s1 + t * (s2 - s1),
+            v1 + t * (v2 - v1),
+            a1 + t * (a2 - a1),
+        )
+    }
+
+    /// Blend this color with the other one, in the [Oklab](https://bottosson.github.io/posts/oklab/) color-space. `t` in the range [0..1].
+    #[must_use]
+    pub fn interpolate_oklab(&self, other: &Color, t: f32) -> Self {
+        let [l1, a1, b1, alpha1] = self.to_oklaba();
         let [l2, a2, b2, alpha2] = other.to_oklaba();
         Self::from_oklaba(
             l1 + t * (l2 - l1),
@@ -591,6 +1574,879 @@ impl Color {
             alpha1 + t * (alpha2 - alpha1),
         )
     }
+
+    /// Blend this color with the other one in the given color space, with the easing function
+    /// `ease` applied to `t` first. `t` in the range [0..1].
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::{ease_in_out, Color, InterpSpace};
+    ///
+    /// let a = Color::new(0.0, 0.0, 0.0, 1.0);
+    /// let b = Color::new(1.0, 1.0, 1.0, 1.0);
+    ///
+    /// let c = a.interpolate_eased(&b, 0.25, InterpSpace::Rgb, ease_in_out);
+    ///
+    /// assert_eq!(c, a.interpolate_rgb(&b, ease_in_out(0.25)));
+    /// ```
+    #[must_use]
+    pub fn interpolate_eased(
+        &self,
+        other: &Color,
+        t: f32,
+        space: InterpSpace,
+        ease: fn(f32) -> f32,
+    ) -> Self {
+        let t = ease(t);
+        match space {
+            InterpSpace::Rgb => self.interpolate_rgb(other, t),
+            InterpSpace::LinearRgb => self.interpolate_linear_rgb(other, t),
+            InterpSpace::Hsv => self.interpolate_hsv(other, t),
+            InterpSpace::Oklab => self.interpolate_oklab(other, t),
+            InterpSpace::Lab => self.interpolate_lab(other, t),
+            InterpSpace::Lch => self.interpolate_lch(other, t),
+        }
+    }
+
+    /// Mix two colors like paint, `t` in the range [0..1] being the amount of `other`.
+    ///
+    /// Unlike the additive blends above, this mixes in the traditional RYB (red, yellow, blue)
+    /// painter's color wheel, so blue and yellow make green rather than gray. It is a perceptual
+    /// approximation using the RYB model of Sugita and Takahashi, not a physical model of pigments.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let blue = Color::new(0.0, 0.0, 1.0, 1.0);
+    /// let yellow = Color::new(1.0, 1.0, 0.0, 1.0);
+    ///
+    /// assert_eq!(blue.mix_subtractive(&yellow, 0.5).to_rgba8(), [0, 128, 0, 255]);
+    /// ```
+    #[must_use]
+    pub fn mix_subtractive(&self, other: &Color, t: f32) -> Self {
+        let c1 = self.clamp();
+        let c2 = other.clamp();
+        let (r1, y1, b1) = rgb_to_ryb(c1.r, c1.g, c1.b);
+        let (r2, y2, b2) = rgb_to_ryb(c2.r, c2.g, c2.b);
+        let (r, g, b) = ryb_to_rgb(r1 + t * (r2 - r1), y1 + t * (y2 - y1), b1 + t * (b2 - b1));
+        Self::new(r, g, b, c1.a + t * (c2.a - c1.a))
+    }
+
+    /// CIE Y, the luminance of the linear RGB channels with the Rec. 709 / sRGB coefficients:
+    /// `0.2126 * r + 0.7152 * g + 0.0722 * b`. The alpha is ignored, and the channels are not
+    /// clamped, so out of gamut colors can give values outside [0..1].
+    ///
+    /// The crate uses several "luminance" like values:
+    ///
+    /// | Value | Computed from | Used for |
+    /// |-------|---------------|----------|
+    /// | Luma (Y′), not provided | Gamma-encoded sRGB | Video encoding |
+    /// | CIE Y, `luminance_y()` | Linear RGB | Color science, e.g. XYZ |
+    /// | WCAG luminance, `luminance()` | Linear RGB clamped to [0..1] | `contrast_ratio()` |
+    /// | Oklab L, `lightness_oklch()` | Oklab, roughly the cube root of Y | Perceptual lightness |
+    ///
+    /// Luma uses the same coefficients as CIE Y, and the WCAG luminance is the relative luminance
+    /// of the WCAG contrast formula. See [luminance](#method.luminance),
+    /// [contrast_ratio](#method.contrast_ratio) and [lightness_oklch](#method.lightness_oklch).
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// assert_eq!(Color::new(1.0, 1.0, 1.0, 1.0).luminance_y(), 1.0);
+    /// assert_eq!(Color::new(0.0, 1.0, 0.0, 1.0).luminance_y(), 0.7152);
+    /// ```
+    pub fn luminance_y(&self) -> f32 {
+        let [r, g, b, _] = self.to_linear_rgba();
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    }
+
+    /// The [WCAG 2](https://www.w3.org/TR/WCAG21/#dfn-relative-luminance) relative luminance,
+    /// in the range [0..1]: the [CIE Y](#method.luminance_y) of the color clamped into the sRGB
+    /// gamut.
+    ///
+    /// The alpha is ignored, as WCAG assumes opaque colors. To take it into account, composite
+    /// the color over its background first, e.g. with [over](#method.over).
+    pub fn luminance(&self) -> f32 {
+        self.clamp().luminance_y()
+    }
+
+    /// The [WCAG 2](https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio) contrast ratio between this
+    /// color and `other`, `(l1 + 0.05) / (l2 + 0.05)` where `l1` is the
+    /// [luminance](#method.luminance) of the lighter color. In the range [1..21], the same in
+    /// both directions.
+    ///
+    /// The alpha is ignored, as WCAG assumes opaque colors.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let fg = Color::new(0.4, 0.4, 0.4, 1.0);
+    /// let bg = Color::new(1.0, 1.0, 1.0, 1.0);
+    ///
+    /// // WCAG AA for normal text
+    /// assert!(fg.contrast_ratio(&bg) >= 4.5);
+    /// ```
+    pub fn contrast_ratio(&self, other: &Color) -> f32 {
+        let l1 = self.luminance();
+        let l2 = other.luminance();
+        (l1.max(l2) + 0.05) / (l1.min(l2) + 0.05)
+    }
+
+    /// Oklch perceived lightness.
+    pub fn lightness_oklch(&self) -> f32 {
+        self.to_oklaba()[0]
+    }
+
+    /// Oklch chroma.
+    pub fn chroma_oklch(&self) -> f32 {
+        self.to_oklcha()[1]
+    }
+
+    /// Oklch hue angle in degrees [0..360).
+    ///
+    /// The hue is meaningless for achromatic colors (chroma near zero).
+    pub fn hue_oklch_deg(&self) -> f32 {
+        self.to_oklcha()[2].to_degrees()
+    }
+
+    /// A monochromatic scale of `n` colors, from dark to light, with the Oklch hue and chroma of
+    /// this color, e.g. for the steps of a design system palette.
+    ///
+    /// The Oklch lightness is evenly spaced from 0.05 to 0.95 (a single color gets 0.5). Colors
+    /// that would be outside the sRGB gamut have their chroma reduced, keeping lightness and
+    /// hue, see [`GamutMapMethod::OklchChromaReduction`]. The alpha is kept.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let scale = Color::new(0.2, 0.4, 0.8, 1.0).tints_and_shades(10);
+    ///
+    /// assert_eq!(scale.len(), 10);
+    /// assert!(scale[0].lightness_oklch() < scale[9].lightness_oklch());
+    /// ```
+    #[must_use]
+    pub fn tints_and_shades(&self, n: usize) -> Vec<Color> {
+        const MIN: f32 = 0.05;
+        const MAX: f32 = 0.95;
+
+        let [_, c, h, alpha] = self.to_oklcha();
+        (0..n)
+            .map(|i| {
+                let l = if n == 1 {
+                    0.5
+                } else {
+                    MIN + (MAX - MIN) * i as f32 / (n - 1) as f32
+                };
+                Self::from_oklcha(l, c, h, alpha)
+                    .to_srgb_gamut_with(GamutMapMethod::OklchChromaReduction)
+            })
+            .collect()
+    }
+
+    /// A key to sort colors by [Oklch](https://bottosson.github.io/posts/oklab/) hue, then
+    /// lightness, e.g. with `sort_by_key()`, for displaying palettes.
+    ///
+    /// Returns `(hue_bucket, lightness_bucket)`:
+    ///
+    /// * `hue_bucket`: The hue in whole degrees [0..360). Colors with a chroma below 0.02 are
+    ///   treated as grays and all get the bucket 360, so they sort after every other hue.
+    /// * `lightness_bucket`: The lightness in steps of 0.001, [0..1000].
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let mut colors = vec![
+    ///     Color::new(0.5, 0.5, 0.5, 1.0),
+    ///     Color::new(0.0, 0.0, 1.0, 1.0),
+    ///     Color::new(1.0, 0.0, 0.0, 1.0),
+    /// ];
+    /// colors.sort_by_key(|c| c.perceptual_sort_key());
+    ///
+    /// assert_eq!(colors[0], Color::new(1.0, 0.0, 0.0, 1.0));
+    /// assert_eq!(colors[2], Color::new(0.5, 0.5, 0.5, 1.0));
+    /// ```
+    pub fn perceptual_sort_key(&self) -> (u16, u16) {
+        const GRAY_CHROMA: f32 = 0.02;
+
+        let [l, c, h, _] = self.to_oklcha();
+        let hue = if c < GRAY_CHROMA || h.is_nan() {
+            360
+        } else {
+            (h.to_degrees() as u16).min(359)
+        };
+        let lightness = (l.clamp(0.0, 1.0) * 1000.0).round() as u16;
+        (hue, lightness)
+    }
+
+    /// Reduce `colors` to at most `n` representative colors.
+    ///
+    /// Uses a variant of the median cut algorithm in the
+    /// [Oklab](https://bottosson.github.io/posts/oklab/) color-space: starting from a single box
+    /// containing all colors, the box with the widest range along any of the `l`, `a`, `b` axes is
+    /// repeatedly split in two along that axis, until there are `n` boxes or no box can be split
+    /// further. Instead of the median, the split is placed where the sum of squared errors of the
+    /// two halves along the axis is the smallest, which keeps clusters of similar colors together.
+    /// Each box is represented by the average of its colors (alpha included). The result is
+    /// approximate, but deterministic.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let colors = [
+    ///     Color::new(1.0, 0.0, 0.0, 1.0),
+    ///     Color::new(0.9, 0.0, 0.0, 1.0),
+    ///     Color::new(0.0, 0.0, 1.0, 1.0),
+    /// ];
+    /// let palette = Color::quantize(&colors, 2);
+    ///
+    /// assert_eq!(palette.len(), 2);
+    /// ```
+    pub fn quantize(colors: &[Color], n: usize) -> Vec<Color> {
+        if n == 0 || colors.is_empty() {
+            return Vec::new();
+        }
+
+        let mut boxes = vec![colors.iter().map(|c| c.to_oklaba()).collect::<Vec<_>>()];
+
+        while boxes.len() < n {
+            // Find the box and axis with the widest range
+            let mut best: Option<(usize, usize, f32)> = None;
+            for (i, b) in boxes.iter().enumerate() {
+                if b.len() < 2 {
+                    continue;
+                }
+                for axis in 0..3 {
+                    let (min, max) = b.iter().fold((f32::MAX, f32::MIN), |(min, max), c| {
+                        (min.min(c[axis]), max.max(c[axis]))
+                    });
+                    let range = max - min;
+                    let wider = match best {
+                        Some((_, _, r)) => range > r,
+                        None => true,
+                    };
+                    if range > 0.0 && wider {
+                        best = Some((i, axis, range));
+                    }
+                }
+            }
+
+            let (i, axis) = match best {
+                Some((i, axis, _)) => (i, axis),
+                None => break,
+            };
+
+            let mut b = boxes.swap_remove(i);
+            b.sort_by(|x, y| x[axis].partial_cmp(&y[axis]).unwrap_or(Ordering::Equal));
+            let upper = b.split_off(best_split(&b, axis));
+            boxes.push(b);
+            boxes.push(upper);
+        }
+
+        boxes
+            .iter()
+            .map(|b| {
+                let mut sum = [0.0; 4];
+                for c in b {
+                    for (s, v) in sum.iter_mut().zip(c) {
+                        *s += v;
+                    }
+                }
+                let len = b.len() as f32;
+                Color::from_oklaba(sum[0] / len, sum[1] / len, sum[2] / len, sum[3] / len)
+            })
+            .collect()
+    }
+
+    /// Increase (or decrease, if `delta` is negative) the
+    /// [Oklch](https://bottosson.github.io/posts/oklab/) chroma by `delta`, keeping lightness, hue
+    /// and alpha. The chroma never goes below zero.
+    ///
+    /// Unlike HSL saturation, equal steps of Oklch chroma are perceived as roughly equal changes
+    /// in colorfulness regardless of the hue. The result may be outside the sRGB gamut, see
+    /// [saturate](#method.saturate) for a gamut mapped result.
+    #[must_use]
+    pub fn saturate_oklch(&self, delta: f32) -> Self {
+        let [l, c, h, alpha] = self.to_oklcha();
+        Self::from_oklcha(l, (c + delta).max(0.0), h, alpha)
+    }
+
+    /// Set the [Oklch](https://bottosson.github.io/posts/oklab/) chroma, keeping lightness, hue
+    /// and alpha. Negative values are treated as zero.
+    #[must_use]
+    pub fn set_chroma_oklch(&self, value: f32) -> Self {
+        let [l, _, h, alpha] = self.to_oklcha();
+        Self::from_oklcha(l, value.max(0.0), h, alpha)
+    }
+
+    /// Increase the [Oklch](https://bottosson.github.io/posts/oklab/) chroma by `amount`,
+    /// keeping lightness, hue and alpha, and map the result into the sRGB gamut with
+    /// [to_srgb_gamut](#method.to_srgb_gamut). The chroma never goes below zero.
+    ///
+    /// Unlike [saturate_oklch](#method.saturate_oklch), the result is always a displayable color.
+    /// [lighten_in_gamut](#method.lighten_in_gamut) does the same for the lightness. To scale
+    /// the HSL saturation instead, use [saturate_relative](#method.saturate_relative).
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::new(0.8, 0.3, 0.3, 1.0).saturate(1.0);
+    ///
+    /// assert!([c.r, c.g, c.b].iter().all(|t| (0.0..=1.0).contains(t)));
+    /// ```
+    #[must_use]
+    pub fn saturate(&self, amount: f32) -> Self {
+        self.saturate_oklch(amount).to_srgb_gamut()
+    }
+
+    /// Decrease the [Oklch](https://bottosson.github.io/posts/oklab/) chroma by `amount`,
+    /// keeping lightness, hue and alpha, and map the result into the sRGB gamut. Same as
+    /// `saturate(-amount)`; a large enough `amount` gives a gray.
+    #[must_use]
+    pub fn desaturate(&self, amount: f32) -> Self {
+        self.saturate(-amount)
+    }
+
+    /// Set any of the [Oklch](https://bottosson.github.io/posts/oklab/) lightness, chroma and
+    /// hue (in degrees) at once. `None` keeps the current value, and alpha is kept as well.
+    ///
+    /// The lightness is clamped to [0..1], the chroma to be at least zero, and the hue is
+    /// normalized to [0..360). The result may be outside the sRGB gamut.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::new(1.0, 0.0, 0.0, 1.0).with_oklch(None, None, Some(-30.0));
+    ///
+    /// assert!((c.hue_oklch_deg() - 330.0).abs() < 1e-3);
+    /// ```
+    #[must_use]
+    pub fn with_oklch(&self, l: Option<f32>, c: Option<f32>, h_deg: Option<f32>) -> Self {
+        let [l0, c0, h0, alpha] = self.to_oklcha();
+        let l = l.unwrap_or(l0).clamp(0.0, 1.0);
+        let c = c.unwrap_or(c0).max(0.0);
+        let h = h_deg.map_or(h0, |h| h.rem_euclid(360.0).to_radians());
+        Self::from_oklcha(l, c, h, alpha)
+    }
+
+    /// Increase the [Oklch](https://bottosson.github.io/posts/oklab/) lightness by `amount`,
+    /// keeping chroma, hue and alpha. The lightness is clamped to [0..1].
+    ///
+    /// The result may be outside the sRGB gamut, see [lighten_in_gamut](#method.lighten_in_gamut)
+    /// for a gamut mapped result.
+    ///
+    /// # Examples
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let c = csscolorparser::parse("#3366cc")?.lighten(0.1);
+    ///
+    /// assert_eq!(c.to_hex_string(), "#5085ee");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn lighten(&self, amount: f32) -> Self {
+        let [l, c, h, alpha] = self.to_oklcha();
+        Self::from_oklcha((l + amount).clamp(0.0, 1.0), c, h, alpha)
+    }
+
+    /// Decrease the [Oklch](https://bottosson.github.io/posts/oklab/) lightness by `amount`,
+    /// keeping chroma, hue and alpha. The lightness is clamped to [0..1]. Same as
+    /// `lighten(-amount)`.
+    #[must_use]
+    pub fn darken(&self, amount: f32) -> Self {
+        self.lighten(-amount)
+    }
+
+    /// [lighten](#method.lighten), then map the result into the sRGB gamut with
+    /// [to_srgb_gamut](#method.to_srgb_gamut).
+    ///
+    /// Like [saturate](#method.saturate), the result is always a displayable color, so the
+    /// chroma is reduced where the lighter color would be out of the gamut.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::new(0.0, 0.0, 1.0, 1.0).lighten_in_gamut(0.3);
+    ///
+    /// assert!([c.r, c.g, c.b].iter().all(|t| (0.0..=1.0).contains(t)));
+    /// ```
+    #[must_use]
+    pub fn lighten_in_gamut(&self, amount: f32) -> Self {
+        self.lighten(amount).to_srgb_gamut()
+    }
+
+    /// [darken](#method.darken), then map the result into the sRGB gamut with
+    /// [to_srgb_gamut](#method.to_srgb_gamut). Same as `lighten_in_gamut(-amount)`.
+    #[must_use]
+    pub fn darken_in_gamut(&self, amount: f32) -> Self {
+        self.lighten_in_gamut(-amount)
+    }
+
+    /// Increase (or decrease, if `amount` is negative) the HWB whiteness by `amount`, keeping
+    /// hue, blackness and alpha. The whiteness is clamped to [0..1].
+    ///
+    /// If whiteness plus blackness reaches 1 the result is a gray, as defined by `hwb()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::new(1.0, 0.0, 0.0, 1.0).whiten(0.5);
+    ///
+    /// assert_eq!(c.to_rgba8(), [255, 128, 128, 255]);
+    /// ```
+    #[must_use]
+    pub fn whiten(&self, amount: f32) -> Self {
+        let [h, w, b, alpha] = self.to_hwba();
+        Self::from_hwba(h, (w + amount).clamp(0.0, 1.0), b, alpha)
+    }
+
+    /// Increase (or decrease, if `amount` is negative) the HWB blackness by `amount`, keeping
+    /// hue, whiteness and alpha. The blackness is clamped to [0..1].
+    ///
+    /// If whiteness plus blackness reaches 1 the result is a gray, as defined by `hwb()`.
+    #[must_use]
+    pub fn blacken(&self, amount: f32) -> Self {
+        let [h, w, b, alpha] = self.to_hwba();
+        Self::from_hwba(h, w, (b + amount).clamp(0.0, 1.0), alpha)
+    }
+
+    /// Map the color into the sRGB gamut, using the
+    /// [CSS Color 4 gamut mapping](https://www.w3.org/TR/css-color-4/#gamut-mapping) algorithm:
+    /// the Oklch chroma is reduced, keeping lightness and hue, until clipping the result is not
+    /// noticeable.
+    ///
+    /// Colors inside the gamut are returned unchanged.
+    #[must_use]
+    pub fn to_srgb_gamut(&self) -> Self {
+        self.to_srgb_gamut_with(GamutMapMethod::default())
+    }
+
+    /// Map the color into the sRGB gamut, using the given [`GamutMapMethod`].
+    ///
+    /// Colors inside the gamut are returned unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::{Color, GamutMapMethod};
+    ///
+    /// let c = Color::from_oklcha(0.7, 0.4, 2.5, 1.0);
+    ///
+    /// assert_eq!(c.to_srgb_gamut_with(GamutMapMethod::Clip).to_rgba8(), [0, 208, 0, 255]);
+    /// assert_eq!(c.to_srgb_gamut_with(GamutMapMethod::ClipChromaHybrid), c.to_srgb_gamut());
+    /// ```
+    #[must_use]
+    pub fn to_srgb_gamut_with(&self, method: GamutMapMethod) -> Self {
+        const JND: f32 = 0.02;
+        const EPSILON: f32 = 0.0001;
+
+        if method == GamutMapMethod::Clip || self.is_in_srgb_gamut() {
+            return self.clamp();
+        }
+
+        let [l, c, h, alpha] = self.to_oklcha();
+
+        if l >= 1.0 {
+            return Self::new(1.0, 1.0, 1.0, alpha);
+        }
+        if l <= 0.0 {
+            return Self::new(0.0, 0.0, 0.0, alpha);
+        }
+
+        if method == GamutMapMethod::OklchChromaReduction {
+            let mut min = 0.0;
+            let mut max = c;
+            while max - min > EPSILON {
+                let chroma = (min + max) / 2.0;
+                if Self::from_oklcha(l, chroma, h, alpha).is_in_srgb_gamut() {
+                    min = chroma;
+                } else {
+                    max = chroma;
+                }
+            }
+            return Self::from_oklcha(l, min, h, alpha).clamp();
+        }
+
+        let mut clipped = self.clamp();
+        if self.delta_e_ok(&clipped) < JND {
+            return clipped;
+        }
+
+        let mut min = 0.0;
+        let mut max = c;
+        let mut min_in_gamut = true;
+
+        while max - min > EPSILON {
+            let chroma = (min + max) / 2.0;
+            let current = Self::from_oklcha(l, chroma, h, alpha);
+
+            if min_in_gamut && current.is_in_srgb_gamut() {
+                min = chroma;
+                continue;
+            }
+
+            clipped = current.clamp();
+            let e = current.delta_e_ok(&clipped);
+
+            if e < JND {
+                if JND - e < EPSILON {
+                    break;
+                }
+                min_in_gamut = false;
+                min = chroma;
+            } else {
+                max = chroma;
+            }
+        }
+
+        clipped
+    }
+
+    // Inside the sRGB gamut, with a small tolerance for rounding errors.
+    fn is_in_srgb_gamut(&self) -> bool {
+        const EPSILON: f32 = 1e-5;
+        [self.r, self.g, self.b]
+            .iter()
+            .all(|&t| (-EPSILON..=1.0 + EPSILON).contains(&t))
+    }
+
+    /// Composite this color over the `background` color, using the Porter-Duff "source over"
+    /// operator (straight alpha, in the sRGB color-space).
+    #[must_use]
+    pub fn over(&self, background: &Color) -> Self {
+        let a = self.a + background.a * (1.0 - self.a);
+        if a <= 0.0 {
+            return Self::new(0.0, 0.0, 0.0, 0.0);
+        }
+        let blend = |fg: f32, bg: f32| (fg * self.a + bg * background.a * (1.0 - self.a)) / a;
+        Self::new(
+            blend(self.r, background.r),
+            blend(self.g, background.g),
+            blend(self.b, background.b),
+            a,
+        )
+    }
+
+    /// Composite the color over opaque white, e.g. to preview a translucent swatch on a light
+    /// background. Same as `self.over(&Color::new(1.0, 1.0, 1.0, 1.0))`.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::new(1.0, 0.0, 0.0, 0.5);
+    ///
+    /// assert_eq!(c.over_white().to_rgba8(), [255, 128, 128, 255]);
+    /// assert_eq!(c.over_black().to_rgba8(), [128, 0, 0, 255]);
+    /// ```
+    #[must_use]
+    pub fn over_white(&self) -> Self {
+        self.over(&Self::new(1.0, 1.0, 1.0, 1.0))
+    }
+
+    /// Composite the color over opaque black, e.g. to preview a translucent swatch on a dark
+    /// background. Same as `self.over(&Color::new(0.0, 0.0, 0.0, 1.0))`.
+    #[must_use]
+    pub fn over_black(&self) -> Self {
+        self.over(&Self::new(0.0, 0.0, 0.0, 1.0))
+    }
+
+    /// Composite a stack of layers using [over](#method.over). The first element is the bottom
+    /// layer and the last element is the top layer.
+    ///
+    /// Returns transparent black if `layers` is empty.
+    pub fn composite(layers: &[Color]) -> Self {
+        layers
+            .iter()
+            .fold(Self::new(0.0, 0.0, 0.0, 0.0), |acc, layer| layer.over(&acc))
+    }
+
+    /// Pick the candidate with enough contrast against `base`, like the CSS Color 5 draft
+    /// `color-contrast(base vs candidates to target)` function. Contrast is the WCAG 2 contrast
+    /// ratio, ignoring alpha.
+    ///
+    /// With a `target` contrast ratio (e.g. 4.5), the first candidate reaching it is returned.
+    /// Otherwise, or if no candidate reaches it, the candidate with the highest contrast is
+    /// returned, the first one on a tie. If `candidates` is empty, black and white are used.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let base = Color::new(0.0, 0.0, 0.5, 1.0);
+    /// let candidates = [
+    ///     Color::new(0.2, 0.2, 0.2, 1.0),
+    ///     Color::new(1.0, 1.0, 0.0, 1.0),
+    ///     Color::new(1.0, 1.0, 1.0, 1.0),
+    /// ];
+    ///
+    /// let c = Color::pick_highest_contrast(&base, &candidates, None);
+    /// assert_eq!(c.to_css_hex(), "#ffffff");
+    ///
+    /// let c = Color::pick_highest_contrast(&base, &candidates, Some(4.5));
+    /// assert_eq!(c.to_css_hex(), "#ffff00");
+    /// ```
+    pub fn pick_highest_contrast(base: &Color, candidates: &[Color], target: Option<f32>) -> Self {
+        let black_white = [Self::new(0.0, 0.0, 0.0, 1.0), Self::new(1.0, 1.0, 1.0, 1.0)];
+        let candidates = if candidates.is_empty() {
+            &black_white[..]
+        } else {
+            candidates
+        };
+
+        if let Some(target) = target {
+            if let Some(c) = candidates.iter().find(|c| base.contrast_ratio(c) >= target) {
+                return c.clone();
+            }
+        }
+
+        let mut best = &candidates[0];
+        let mut best_ratio = base.contrast_ratio(best);
+        for c in &candidates[1..] {
+            let ratio = base.contrast_ratio(c);
+            if ratio > best_ratio {
+                best = c;
+                best_ratio = ratio;
+            }
+        }
+        best.clone()
+    }
+
+    /// Returns `true` if both colors are equal at 8-bit precision, that is, they have the same
+    /// [to_rgba8](#method.to_rgba8) value (and the same hex string).
+    pub fn equals_css(&self, other: &Color) -> bool {
+        self.to_rgba8() == other.to_rgba8()
+    }
+
+    /// Color difference as the Euclidean distance in
+    /// [Oklab](https://bottosson.github.io/posts/oklab/). The alpha is ignored.
+    ///
+    /// Cheap and perceptually fairly uniform; it is the difference used by the CSS gamut mapping.
+    /// Black and white are 1.0 apart, and about 0.02 is a just noticeable difference.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let a = Color::from_rgba8(200, 100, 50, 255);
+    /// let b = Color::from_rgba8(201, 100, 51, 255);
+    ///
+    /// assert!(a.delta_e_ok(&b) < 0.02);
+    /// ```
+    pub fn delta_e_ok(&self, other: &Color) -> f32 {
+        let [l1, a1, b1, _] = self.to_oklaba();
+        let [l2, a2, b2, _] = other.to_oklaba();
+        ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt()
+    }
+
+    /// CIE76 color difference, the Euclidean distance in CIE Lab (D50). The alpha is ignored.
+    ///
+    /// Black and white are 100 apart. See [delta_e_2000](#method.delta_e_2000) for a more
+    /// perceptually accurate difference.
+    pub fn delta_e_76(&self, other: &Color) -> f32 {
+        let [l1, a1, b1, _] = self.to_laba();
+        let [l2, a2, b2, _] = other.to_laba();
+        ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt()
+    }
+
+    /// CIEDE2000 color difference, in CIE Lab (D50). The alpha is ignored.
+    ///
+    /// A difference below about 1 is not perceptible, and about 2.3 is a commonly used just
+    /// noticeable difference.
+    pub fn delta_e_2000(&self, other: &Color) -> f32 {
+        let [l1, a1, b1, _] = self.to_laba();
+        let [l2, a2, b2, _] = other.to_laba();
+        lab::delta_e_2000([l1, a1, b1], [l2, a2, b2])
+    }
+
+    /// Returns `true` if the colors look the same: their
+    /// [CIEDE2000 difference](#method.delta_e_2000) is below `threshold`. The alpha is ignored.
+    ///
+    /// 2.3, the just noticeable difference, is a good default `threshold`.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let a = Color::from_rgba8(200, 100, 50, 255);
+    ///
+    /// assert!(a.visually_equal(&Color::from_rgba8(201, 100, 51, 255), 2.3));
+    /// assert!(!a.visually_equal(&Color::from_rgba8(200, 110, 50, 255), 2.3));
+    /// ```
+    pub fn visually_equal(&self, other: &Color, threshold: f32) -> bool {
+        self.delta_e_2000(other) < threshold
+    }
+
+    /// Returns `true` if the color's [to_rgba8](#method.to_rgba8) value is `rgba`.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::new(1.0, 0.5, 0.0, 1.0);
+    ///
+    /// assert!(c.eq_rgba8([255, 128, 0, 255]));
+    /// ```
+    pub fn eq_rgba8(&self, rgba: [u8; 4]) -> bool {
+        self.to_rgba8() == rgba
+    }
+
+    /// Signed shortest angular distance, in degrees, from this color's HSL hue to the other
+    /// color's HSL hue. The result is in the range (-180..180].
+    ///
+    /// Returns `NaN` if either color is achromatic (the hue is undefined).
+    pub fn hue_difference(&self, other: &Color) -> f32 {
+        let [h1, s1, _, _] = self.to_hsla();
+        let [h2, s2, _, _] = other.to_hsla();
+        if s1 == 0.0 || s2 == 0.0 {
+            return f32::NAN;
+        }
+        let delta = (((h2 - h1) % 360.0) + 540.0) % 360.0 - 180.0;
+        if delta == -180.0 {
+            return 180.0;
+        }
+        delta
+    }
+
+    /// Multiply the HSL saturation by `factor`, keeping hue, lightness and alpha. The saturation
+    /// is clamped to [0..1].
+    ///
+    /// So `saturate_relative(1.2)` increases the saturation by 20% of its current value, and
+    /// `saturate_relative(0.0)` gives a gray. See [saturate](#method.saturate) for a
+    /// perceptually more uniform change of the colorfulness.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_hsla(0.0, 0.5, 0.5, 1.0).saturate_relative(1.2);
+    ///
+    /// assert!((c.to_hsla()[1] - 0.6).abs() < 1e-6);
+    /// ```
+    #[must_use]
+    pub fn saturate_relative(&self, factor: f32) -> Self {
+        let [h, s, l, a] = self.to_hsla();
+        Self::from_hsla(h, (s * factor).clamp(0.0, 1.0), l, a)
+    }
+
+    /// Returns a color with the HSL hue set to `target_hue` (in degrees), keeping saturation,
+    /// lightness and alpha.
+    ///
+    /// Achromatic colors are returned unchanged.
+    #[must_use]
+    pub fn hue_rotate_to(&self, target_hue: f32) -> Self {
+        let [_, s, l, a] = self.to_hsla();
+        if s == 0.0 {
+            return self.clone();
+        }
+        Self::from_hsla(target_hue, s, l, a)
+    }
+
+    /// Get the nearest color index in the xterm 256-color palette. The alpha is ignored.
+    ///
+    /// Only the 6×6×6 color cube (indices 16 to 231) and the grayscale ramp (indices 232 to 255)
+    /// are considered, since the first 16 system colors are often redefined by terminal themes.
+    /// Each 8-bit channel is mapped to the nearest cube level (0, 95, 135, 175, 215, 255), and
+    /// the channel average to the nearest gray level (8, 18, .., 238). The candidate with the
+    /// smallest squared RGB distance wins; on a tie the cube color is used.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// assert_eq!(Color::new(1.0, 0.0, 0.0, 1.0).to_ansi256(), 196);
+    /// assert_eq!(Color::from_rgba8(128, 128, 128, 255).to_ansi256(), 244);
+    /// ```
+    pub fn to_ansi256(&self) -> u8 {
+        const LEVELS: [i32; 6] = [0, 95, 135, 175, 215, 255];
+
+        let [r, g, b, _] = self.to_rgba8();
+        let rgb = [r as i32, g as i32, b as i32];
+
+        let nearest_level = |v: i32| (0..6).min_by_key(|&i| (LEVELS[i] - v).abs()).unwrap_or(0);
+        let distance = |c: [i32; 3]| -> i32 { (0..3).map(|i| (c[i] - rgb[i]).pow(2)).sum() };
+
+        let [ri, gi, bi] = rgb.map(nearest_level);
+        let cube = [LEVELS[ri], LEVELS[gi], LEVELS[bi]];
+        let cube_index = 16 + 36 * ri + 6 * gi + bi;
+
+        let avg = rgb.iter().sum::<i32>() as f32 / 3.0;
+        let gray_i = ((avg - 8.0) / 10.0).round().clamp(0.0, 23.0) as i32;
+        let gray = 8 + 10 * gray_i;
+
+        if distance([gray; 3]) < distance(cube) {
+            (232 + gray_i) as u8
+        } else {
+            cube_index as u8
+        }
+    }
+
+    /// Get the color of an index in the xterm 256-color palette, the reverse of
+    /// [to_ansi256](#method.to_ansi256).
+    ///
+    /// * 0 to 15: The system colors, with the common xterm values: black `#000000`, maroon
+    ///   `#800000`, green `#008000`, olive `#808000`, navy `#000080`, purple `#800080`, teal
+    ///   `#008080`, silver `#c0c0c0`, gray `#808080`, red `#ff0000`, lime `#00ff00`, yellow
+    ///   `#ffff00`, blue `#0000ff`, fuchsia `#ff00ff`, aqua `#00ffff` and white `#ffffff`.
+    ///   Terminal themes often redefine these.
+    /// * 16 to 231: The 6×6×6 color cube, with the channel levels 0, 95, 135, 175, 215 and 255.
+    /// * 232 to 255: The grayscale ramp, 8, 18, .., 238.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// assert_eq!(Color::from_ansi256(196).to_rgba8(), [255, 0, 0, 255]);
+    /// assert_eq!(Color::from_ansi256(244).to_rgba8(), [128, 128, 128, 255]);
+    /// ```
+    pub fn from_ansi256(index: u8) -> Self {
+        const SYSTEM: [[u8; 3]; 16] = [
+            [0, 0, 0],
+            [128, 0, 0],
+            [0, 128, 0],
+            [128, 128, 0],
+            [0, 0, 128],
+            [128, 0, 128],
+            [0, 128, 128],
+            [192, 192, 192],
+            [128, 128, 128],
+            [255, 0, 0],
+            [0, 255, 0],
+            [255, 255, 0],
+            [0, 0, 255],
+            [255, 0, 255],
+            [0, 255, 255],
+            [255, 255, 255],
+        ];
+        const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+        let [r, g, b] = match index {
+            0..=15 => SYSTEM[index as usize],
+            16..=231 => {
+                let i = (index - 16) as usize;
+                [LEVELS[i / 36], LEVELS[(i / 6) % 6], LEVELS[i % 6]]
+            }
+            _ => [8 + 10 * (index - 232); 3],
+        };
+        Self::from_rgba8(r, g, b, 255)
+    }
+
+    /// Get the ANSI 24-bit (truecolor) escape sequence setting the terminal foreground to this
+    /// color, e.g. `"\x1b[38;2;255;0;0m"`. The alpha is ignored.
+    #[must_use]
+    pub fn to_ansi_truecolor_escape(&self) -> String {
+        let [r, g, b, _] = self.to_rgba8();
+        format!("\x1b[38;2;{};{};{}m", r, g, b)
+    }
 }
 
 impl Default for Color {
@@ -610,6 +2466,24 @@ impl fmt::Display for Color {
     }
 }
 
+/// The hexadecimal digits of [`Color::to_hex_string()`], without the `#`, e.g. `ff0000`, or
+/// `ff000080` if the color is not fully opaque. The alternate flag (`{:#x}`) adds the `#`, giving
+/// the CSS hex color.
+impl fmt::LowerHex for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = self.to_hex_string();
+        f.pad(if f.alternate() { &s } else { &s[1..] })
+    }
+}
+
+/// Like [`LowerHex`](#impl-LowerHex-for-Color), with uppercase digits.
+impl fmt::UpperHex for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = self.to_hex_string().to_uppercase();
+        f.pad(if f.alternate() { &s } else { &s[1..] })
+    }
+}
+
 impl FromStr for Color {
     type Err = ParseColorError;
 
@@ -650,6 +2524,66 @@ impl From<[f32; 3]> for Color {
     }
 }
 
+/// Collect a color from its components in the order `r`, `g`, `b`, `a`, as yielded by
+/// [`Color::components()`]. At most 4 values are read. A missing alpha is 1, and missing `r`,
+/// `g` or `b` values are 0.
+impl FromIterator<f32> for Color {
+    fn from_iter<I: IntoIterator<Item = f32>>(iter: I) -> Self {
+        let mut v = [0.0, 0.0, 0.0, 1.0];
+        for (i, t) in iter.into_iter().take(4).enumerate() {
+            v[i] = t;
+        }
+        v.into()
+    }
+}
+
+/// Channelwise addition, including the alpha. These arithmetic operators are raw math on the
+/// sRGB values, not perceptual blending, and the results are not clamped.
+///
+/// ```
+/// use csscolorparser::Color;
+///
+/// let a = Color::new(1.0, 0.0, 0.0, 1.0);
+/// let b = Color::new(0.0, 0.0, 1.0, 0.5);
+///
+/// assert_eq!(a * 0.5 + b * 0.5, Color::new(0.5, 0.0, 0.5, 0.75));
+/// ```
+impl Add for Color {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(
+            self.r + rhs.r,
+            self.g + rhs.g,
+            self.b + rhs.b,
+            self.a + rhs.a,
+        )
+    }
+}
+
+/// Channelwise subtraction, including the alpha. The result is not clamped.
+impl Sub for Color {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(
+            self.r - rhs.r,
+            self.g - rhs.g,
+            self.b - rhs.b,
+            self.a - rhs.a,
+        )
+    }
+}
+
+/// Multiply every channel, including the alpha, by a scalar. The result is not clamped.
+impl Mul<f32> for Color {
+    type Output = Self;
+
+    fn mul(self, rhs: f32) -> Self {
+        Self::new(self.r * rhs, self.g * rhs, self.b * rhs, self.a * rhs)
+    }
+}
+
 impl From<[f64; 4]> for Color {
     fn from([r, g, b, a]: [f64; 4]) -> Self {
         Self {
@@ -891,6 +2825,119 @@ fn rgb_to_hwb(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
     (hue, white, black)
 }
 
+// RGB to RYB, from "Computational RYB Color Model and its Applications" (Sugita, Takahashi).
+fn rgb_to_ryb(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+    // Remove the whiteness
+    let w = r.min(g.min(b));
+    let (r, g, mut b) = (r - w, g - w, b - w);
+    let max_g = r.max(g.max(b));
+
+    // Red and green make yellow
+    let mut y = r.min(g);
+    let (r, mut g) = (r - y, g - y);
+
+    // Green is split between yellow and blue
+    if b > 0.0 && g > 0.0 {
+        b /= 2.0;
+        g /= 2.0;
+    }
+    y += g;
+    b += g;
+
+    // Normalize to the original intensity
+    let max_y = r.max(y.max(b));
+    let n = if max_y > 0.0 { max_g / max_y } else { 0.0 };
+    (r * n + w, y * n + w, b * n + w)
+}
+
+// Inverse of `rgb_to_ryb`.
+fn ryb_to_rgb(r: f32, y: f32, b: f32) -> (f32, f32, f32) {
+    let w = r.min(y.min(b));
+    let (r, y, mut b) = (r - w, y - w, b - w);
+    let max_y = r.max(y.max(b));
+
+    // Yellow and blue make green
+    let mut g = y.min(b);
+    let y = y - g;
+    b -= g;
+
+    if b > 0.0 && g > 0.0 {
+        b *= 2.0;
+        g *= 2.0;
+    }
+    let r = r + y;
+    g += y;
+
+    let max_g = r.max(g.max(b));
+    let n = if max_g > 0.0 { max_y / max_g } else { 0.0 };
+    (r * n + w, g * n + w, b * n + w)
+}
+
+// Index at which to split the colors (sorted along `axis`) so that the sum of squared errors of
+// both halves along the axis is minimal.
+fn best_split(colors: &[[f32; 4]], axis: usize) -> usize {
+    let n = colors.len();
+    let total: f32 = colors.iter().map(|c| c[axis]).sum();
+    let total_sq: f32 = colors.iter().map(|c| c[axis] * c[axis]).sum();
+
+    let mut sum = 0.0;
+    let mut sum_sq = 0.0;
+    let mut best = (n / 2, f32::MAX);
+
+    for (k, c) in colors.iter().enumerate().take(n - 1) {
+        sum += c[axis];
+        sum_sq += c[axis] * c[axis];
+        let left = (k + 1) as f32;
+        let right = (n - k - 1) as f32;
+        let sse = (sum_sq - sum * sum / left)
+            + ((total_sq - sum_sq) - (total - sum) * (total - sum) / right);
+        if sse < best.1 {
+            best = (k + 1, sse);
+        }
+    }
+
+    best.0
+}
+
+// Format float with the specified number of decimal places, trailing zeros removed.
+fn fmt_float(t: f32, precision: usize) -> String {
+    let s = format!("{:.1$}", t, precision);
+    let s = if s.contains('.') {
+        s.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &s
+    };
+    if s == "-0" {
+        return "0".into();
+    }
+    s.to_string()
+}
+
+// Format hue angle in degrees, or `none` if the hue is powerless (or `NaN`).
+fn fmt_hue(h: f32, powerless: bool) -> String {
+    if powerless || h.is_nan() {
+        return "none".into();
+    }
+    fmt_float(h, 2)
+}
+
+// Format alpha as CSS ` / NN%`, or an empty string if the color is opaque.
+fn fmt_alpha(alpha: f32) -> String {
+    fmt_alpha_with(alpha, AlphaStyle::Percentage)
+}
+
+// Format alpha as CSS ` / NN%` or ` / 0.N`, or an empty string if the color is opaque.
+fn fmt_alpha_with(alpha: f32, style: AlphaStyle) -> String {
+    if alpha >= 1.0 {
+        return String::new();
+    }
+    let alpha = alpha.max(0.0);
+    match style {
+        AlphaStyle::Percentage => format!(" / {}%", fmt_float(alpha * 100.0, 2)),
+        AlphaStyle::Decimal => format!(" / {}", fmt_float(alpha, 4)),
+    }
+}
+
 #[inline]
 fn normalize_angle(t: f32) -> f32 {
     let mut t = t % 360.0;
@@ -906,7 +2953,6 @@ fn interp_angle(a0: f32, a1: f32, t: f32) -> f32 {
     (a0 + t * delta + 360.0) % 360.0
 }
 
-#[cfg(feature = "lab")]
 #[inline]
 fn interp_angle_rad(a0: f32, a1: f32, t: f32) -> f32 {
     let delta = (((a1 - a0) % TAU) + PI_3) % TAU - PI;
@@ -944,6 +2990,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_fmt_float() {
+        let data = [
+            ((0.0, 2), "0"),
+            ((-0.0001, 2), "0"),
+            ((1.5, 0), "2"),
+            ((30.5, 1), "30.5"),
+            ((30.5, 3), "30.5"),
+            ((290.0, 2), "290"),
+            ((-12.345, 2), "-12.35"),
+            ((100.0, 0), "100"),
+        ];
+        for ((t, precision), expected) in data {
+            assert_eq!(fmt_float(t, precision), expected);
+        }
+    }
+
     #[test]
     fn test_interp_angle() {
         let data = vec![
@@ -958,6 +3021,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_ryb_roundtrip() {
+        let data = [
+            (0.0, 0.0, 0.0),
+            (1.0, 1.0, 1.0),
+            (1.0, 0.0, 0.0),
+            (0.0, 1.0, 0.0),
+            (0.0, 0.0, 1.0),
+            (1.0, 1.0, 0.0),
+            (0.2, 0.5, 0.8),
+            (0.9, 0.3, 0.6),
+        ];
+        for (r, g, b) in data {
+            let (ry, y, bl) = rgb_to_ryb(r, g, b);
+            let (r2, g2, b2) = ryb_to_rgb(ry, y, bl);
+            assert!((r - r2).abs() < 1e-5, "{:?}", (r, g, b));
+            assert!((g - g2).abs() < 1e-5, "{:?}", (r, g, b));
+            assert!((b - b2).abs() < 1e-5, "{:?}", (r, g, b));
+        }
+    }
+
     #[cfg(feature = "rust-rgb")]
     #[test]
     fn test_convert_rust_rgb_to_color() {
@@ -0,0 +1,20 @@
+// Easing functions for `Color::interpolate_eased()`, mapping [0..1] onto [0..1].
+
+/// Cubic ease-in: starts slow and accelerates. `t³`
+pub fn ease_in(t: f32) -> f32 {
+    t * t * t
+}
+
+/// Cubic ease-out: starts fast and decelerates. `1 - (1 - t)³`
+pub fn ease_out(t: f32) -> f32 {
+    1.0 - (1.0 - t).powi(3)
+}
+
+/// Cubic ease-in-out: slow at both ends, symmetric around `t = 0.5`.
+pub fn ease_in_out(t: f32) -> f32 {
+    if t < 0.5 {
+        4.0 * t * t * t
+    } else {
+        1.0 - (2.0 - 2.0 * t).powi(3) / 2.0
+    }
+}
@@ -0,0 +1,380 @@
+// CIE Lab and LCh conversions, using the D50 reference white as defined in CSS Color 4.
+// https://www.w3.org/TR/css-color-4/#color-conversion-code
+//
+// The matrix math is done in f64 to keep white and gray achromatic.
+
+#![allow(clippy::excessive_precision)]
+
+use std::f32::consts::TAU;
+
+// D50 reference white
+const D50: [f64; 3] = [0.3457 / 0.3585, 1.0, (1.0 - 0.3457 - 0.3585) / 0.3585];
+
+const EPSILON: f64 = 216.0 / 24389.0;
+const KAPPA: f64 = 24389.0 / 27.0;
+
+#[inline]
+fn to_f64([a, b, c]: [f32; 3]) -> [f64; 3] {
+    [a as f64, b as f64, c as f64]
+}
+
+#[inline]
+fn to_f32([a, b, c]: [f64; 3]) -> [f32; 3] {
+    [a as f32, b as f32, c as f32]
+}
+
+#[inline]
+fn mul3(m: &[[f64; 3]; 3], [x, y, z]: [f64; 3]) -> [f64; 3] {
+    [
+        m[0][0] * x + m[0][1] * y + m[0][2] * z,
+        m[1][0] * x + m[1][1] * y + m[1][2] * z,
+        m[2][0] * x + m[2][1] * y + m[2][2] * z,
+    ]
+}
+
+// Linear sRGB to CIE XYZ (D65)
+pub(crate) fn linear_rgb_to_xyz(rgb: [f64; 3]) -> [f64; 3] {
+    const M: [[f64; 3]; 3] = [
+        [0.41239079926595934, 0.357584339383878, 0.1804807884018343],
+        [0.21263900587151027, 0.715168678767756, 0.07219231536073371],
+        [0.01933081871559182, 0.11919477979462598, 0.9505321522496607],
+    ];
+    mul3(&M, rgb)
+}
+
+// CIE XYZ (D65) to linear sRGB
+pub(crate) fn xyz_to_linear_rgb(xyz: [f64; 3]) -> [f64; 3] {
+    const M: [[f64; 3]; 3] = [
+        [3.2409699419045226, -1.537383177570094, -0.4986107602930034],
+        [-0.9692436362808796, 1.8759675015077202, 0.04155505740717559],
+        [
+            0.05563007969699366,
+            -0.20397695888897652,
+            1.0569715142428786,
+        ],
+    ];
+    mul3(&M, xyz)
+}
+
+// Linear Display P3 to CIE XYZ (D65)
+fn linear_p3_to_xyz(rgb: [f64; 3]) -> [f64; 3] {
+    const M: [[f64; 3]; 3] = [
+        [
+            608311.0 / 1250200.0,
+            189793.0 / 714400.0,
+            198249.0 / 1000160.0,
+        ],
+        [
+            35783.0 / 156275.0,
+            247089.0 / 357200.0,
+            198249.0 / 2500400.0,
+        ],
+        [0.0, 32229.0 / 714400.0, 5220557.0 / 5000800.0],
+    ];
+    mul3(&M, rgb)
+}
+
+// CIE XYZ (D65) to linear Display P3
+fn xyz_to_linear_p3(xyz: [f64; 3]) -> [f64; 3] {
+    const M: [[f64; 3]; 3] = [
+        [
+            446124.0 / 178915.0,
+            -333277.0 / 357830.0,
+            -72051.0 / 178915.0,
+        ],
+        [-14852.0 / 17905.0, 63121.0 / 35810.0, 423.0 / 17905.0],
+        [11844.0 / 330415.0, -50337.0 / 660830.0, 316169.0 / 330415.0],
+    ];
+    mul3(&M, xyz)
+}
+
+// Bradford chromatic adaptation from D65 to D50
+pub(crate) fn d65_to_d50(xyz: [f64; 3]) -> [f64; 3] {
+    const M: [[f64; 3]; 3] = [
+        [
+            1.0479297925449969,
+            0.022946870601609597,
+            -0.050192266289205215,
+        ],
+        [0.029627808770055882, 0.99043442675388, -0.0170737990634188],
+        [
+            -0.009243040646204508,
+            0.015055191490298145,
+            0.7518742814281372,
+        ],
+    ];
+    mul3(&M, xyz)
+}
+
+// Bradford chromatic adaptation from D50 to D65
+pub(crate) fn d50_to_d65(xyz: [f64; 3]) -> [f64; 3] {
+    const M: [[f64; 3]; 3] = [
+        [
+            0.955473421488075,
+            -0.023098454948764585,
+            0.06325924320057066,
+        ],
+        [
+            -0.02836970933386378,
+            1.0099953980813041,
+            0.021041441191917303,
+        ],
+        [
+            0.012314014864481979,
+            -0.020507649298898957,
+            1.330365926242124,
+        ],
+    ];
+    mul3(&M, xyz)
+}
+
+// CIE XYZ (D50) to CIE Lab
+pub(crate) fn xyz_to_lab(xyz: [f64; 3]) -> [f64; 3] {
+    let f = |t: f64| {
+        if t > EPSILON {
+            t.cbrt()
+        } else {
+            (KAPPA * t + 16.0) / 116.0
+        }
+    };
+    let fx = f(xyz[0] / D50[0]);
+    let fy = f(xyz[1] / D50[1]);
+    let fz = f(xyz[2] / D50[2]);
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+// CIE Lab to CIE XYZ (D50)
+pub(crate) fn lab_to_xyz([l, a, b]: [f64; 3]) -> [f64; 3] {
+    let fy = (l + 16.0) / 116.0;
+    let fx = a / 500.0 + fy;
+    let fz = fy - b / 200.0;
+    let x = if fx.powi(3) > EPSILON {
+        fx.powi(3)
+    } else {
+        (116.0 * fx - 16.0) / KAPPA
+    };
+    let y = if l > KAPPA * EPSILON {
+        fy.powi(3)
+    } else {
+        l / KAPPA
+    };
+    let z = if fz.powi(3) > EPSILON {
+        fz.powi(3)
+    } else {
+        (116.0 * fz - 16.0) / KAPPA
+    };
+    [x * D50[0], y * D50[1], z * D50[2]]
+}
+
+// Linear sRGB to CIE XYZ (D65)
+pub(crate) fn linear_rgb_to_xyz_d65(rgb: [f32; 3]) -> [f32; 3] {
+    to_f32(linear_rgb_to_xyz(to_f64(rgb)))
+}
+
+// Linear sRGB to CIE XYZ (D50)
+pub(crate) fn linear_rgb_to_xyz_d50(rgb: [f32; 3]) -> [f32; 3] {
+    to_f32(d65_to_d50(linear_rgb_to_xyz(to_f64(rgb))))
+}
+
+// CIE XYZ (D65) to linear sRGB
+pub(crate) fn xyz_d65_to_linear_rgb(xyz: [f32; 3]) -> [f32; 3] {
+    to_f32(xyz_to_linear_rgb(to_f64(xyz)))
+}
+
+// CIE XYZ (D50) to linear sRGB
+pub(crate) fn xyz_d50_to_linear_rgb(xyz: [f32; 3]) -> [f32; 3] {
+    to_f32(xyz_to_linear_rgb(d50_to_d65(to_f64(xyz))))
+}
+
+// Linear Display P3 to linear sRGB
+pub(crate) fn linear_p3_to_linear_rgb(rgb: [f32; 3]) -> [f32; 3] {
+    to_f32(xyz_to_linear_rgb(linear_p3_to_xyz(to_f64(rgb))))
+}
+
+// Linear sRGB to linear Display P3
+pub(crate) fn linear_rgb_to_linear_p3(rgb: [f32; 3]) -> [f32; 3] {
+    to_f32(xyz_to_linear_p3(linear_rgb_to_xyz(to_f64(rgb))))
+}
+
+// Linear sRGB to CIE Lab
+pub(crate) fn linear_rgb_to_lab(rgb: [f32; 3]) -> [f32; 3] {
+    to_f32(xyz_to_lab(d65_to_d50(linear_rgb_to_xyz(to_f64(rgb)))))
+}
+
+// CIE Lab to linear sRGB
+pub(crate) fn lab_to_linear_rgb(lab: [f32; 3]) -> [f32; 3] {
+    to_f32(xyz_to_linear_rgb(d50_to_d65(lab_to_xyz(to_f64(lab)))))
+}
+
+// Lab to LCh, hue in radians [0..2π)
+pub(crate) fn lab_to_lch([l, a, b]: [f32; 3]) -> [f32; 3] {
+    let c = (a * a + b * b).sqrt();
+    let h = b.atan2(a).rem_euclid(TAU);
+    [l, c, h]
+}
+
+// LCh to Lab, hue in radians
+pub(crate) fn lch_to_lab([l, c, h]: [f32; 3]) -> [f32; 3] {
+    [l, c * h.cos(), c * h.sin()]
+}
+
+// CIEDE2000 color difference between two CIE Lab colors.
+// "The CIEDE2000 Color-Difference Formula: Implementation Notes, Supplementary Test Data, and
+// Mathematical Observations" (Sharma, Wu, Dalal)
+pub(crate) fn delta_e_2000(lab1: [f32; 3], lab2: [f32; 3]) -> f32 {
+    let [l1, a1, b1] = to_f64(lab1);
+    let [l2, a2, b2] = to_f64(lab2);
+    let pow25_7 = 25f64.powi(7);
+
+    let c_bar = (a1.hypot(b1) + a2.hypot(b2)) / 2.0;
+    let g = 0.5 * (1.0 - (c_bar.powi(7) / (c_bar.powi(7) + pow25_7)).sqrt());
+    let a1p = a1 * (1.0 + g);
+    let a2p = a2 * (1.0 + g);
+    let c1p = a1p.hypot(b1);
+    let c2p = a2p.hypot(b2);
+
+    // Hue in degrees [0..360), 0 for achromatic colors
+    let hue = |b: f64, a: f64| {
+        if a == 0.0 && b == 0.0 {
+            0.0
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.0)
+        }
+    };
+    let h1p = hue(b1, a1p);
+    let h2p = hue(b2, a2p);
+    let chromatic = c1p * c2p != 0.0;
+
+    let dl = l2 - l1;
+    let dc = c2p - c1p;
+    let dh = if !chromatic {
+        0.0
+    } else if h2p - h1p > 180.0 {
+        h2p - h1p - 360.0
+    } else if h2p - h1p < -180.0 {
+        h2p - h1p + 360.0
+    } else {
+        h2p - h1p
+    };
+    let dh = 2.0 * (c1p * c2p).sqrt() * (dh / 2.0).to_radians().sin();
+
+    let l_bar = (l1 + l2) / 2.0;
+    let cp_bar = (c1p + c2p) / 2.0;
+    let hp_bar = if !chromatic {
+        h1p + h2p
+    } else if (h1p - h2p).abs() <= 180.0 {
+        (h1p + h2p) / 2.0
+    } else if h1p + h2p < 360.0 {
+        (h1p + h2p + 360.0) / 2.0
+    } else {
+        (h1p + h2p - 360.0) / 2.0
+    };
+
+    let cos_deg = |t: f64| t.to_radians().cos();
+    let t = 1.0 - 0.17 * cos_deg(hp_bar - 30.0)
+        + 0.24 * cos_deg(2.0 * hp_bar)
+        + 0.32 * cos_deg(3.0 * hp_bar + 6.0)
+        - 0.20 * cos_deg(4.0 * hp_bar - 63.0);
+    let d_theta = 30.0 * (-((hp_bar - 275.0) / 25.0).powi(2)).exp();
+    let rc = 2.0 * (cp_bar.powi(7) / (cp_bar.powi(7) + pow25_7)).sqrt();
+    let sl = 1.0 + 0.015 * (l_bar - 50.0).powi(2) / (20.0 + (l_bar - 50.0).powi(2)).sqrt();
+    let sc = 1.0 + 0.045 * cp_bar;
+    let sh = 1.0 + 0.015 * cp_bar * t;
+    let rt = -(2.0 * d_theta).to_radians().sin() * rc;
+
+    let (l, c, h) = (dl / sl, dc / sc, dh / sh);
+    (l * l + c * c + h * h + rt * c * h).sqrt() as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lab_white_black() {
+        let [l, a, b] = linear_rgb_to_lab([1.0, 1.0, 1.0]);
+        assert!((l - 100.0).abs() < 1e-3);
+        assert!(a.abs() < 1e-3);
+        assert!(b.abs() < 1e-3);
+
+        assert_eq!(linear_rgb_to_lab([0.0, 0.0, 0.0]), [0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_lab_roundtrip() {
+        let data = [
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [0.2, 0.5, 0.8],
+            [0.001, 0.002, 0.0005],
+        ];
+        for rgb in data {
+            let x = lab_to_linear_rgb(linear_rgb_to_lab(rgb));
+            for i in 0..3 {
+                assert!((rgb[i] - x[i]).abs() < 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn test_p3_roundtrip() {
+        // sRGB white is Display P3 white
+        let [r, g, b] = linear_rgb_to_linear_p3([1.0, 1.0, 1.0]);
+        assert!((r - 1.0).abs() < 1e-5 && (g - 1.0).abs() < 1e-5 && (b - 1.0).abs() < 1e-5);
+
+        let data = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.2, 0.5, 0.8]];
+        for rgb in data {
+            let x = linear_p3_to_linear_rgb(linear_rgb_to_linear_p3(rgb));
+            for i in 0..3 {
+                assert!((rgb[i] - x[i]).abs() < 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn test_delta_e_2000() {
+        // Sharma, Wu, Dalal test data
+        let data = [
+            ([50.0, 2.6772, -79.7751], [50.0, 0.0, -82.7485], 2.0425),
+            ([50.0, 3.1571, -77.2803], [50.0, 0.0, -82.7485], 2.8615),
+            ([50.0, -1.3802, -84.2814], [50.0, 0.0, -82.7485], 1.0),
+            ([50.0, 0.0, 0.0], [50.0, -1.0, 2.0], 2.3669),
+            ([50.0, 2.49, -0.001], [50.0, -2.49, 0.0011], 7.2195),
+            ([50.0, 2.5, 0.0], [73.0, 25.0, -18.0], 27.1492),
+            ([50.0, 2.5, 0.0], [50.0, 3.1736, 0.5854], 1.0),
+            (
+                [60.2574, -34.0099, 36.2677],
+                [60.4626, -34.1751, 39.4387],
+                1.2644,
+            ),
+            (
+                [22.7233, 20.0904, -46.694],
+                [23.0331, 14.973, -42.5619],
+                2.0373,
+            ),
+            (
+                [90.8027, -2.0831, 1.441],
+                [91.1528, -1.6435, 0.0447],
+                1.4441,
+            ),
+            ([2.0776, 0.0795, -1.135], [0.9033, -0.0636, -0.5514], 0.9082),
+        ];
+        for (lab1, lab2, expected) in data {
+            let d = delta_e_2000(lab1, lab2);
+            assert!((d - expected).abs() < 1e-4, "{:?} {:?} {}", lab1, lab2, d);
+            // Symmetric
+            assert!((delta_e_2000(lab2, lab1) - d).abs() < 1e-5);
+        }
+        assert_eq!(delta_e_2000([50.0, 10.0, 10.0], [50.0, 10.0, 10.0]), 0.0);
+    }
+
+    #[test]
+    fn test_lab_known_values() {
+        // sRGB red in CIE Lab (D50)
+        let [l, a, b] = linear_rgb_to_lab([1.0, 0.0, 0.0]);
+        assert!((l - 54.29).abs() < 0.01);
+        assert!((a - 80.80).abs() < 0.01);
+        assert!((b - 69.89).abs() < 0.01);
+    }
+}
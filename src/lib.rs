@@ -5,7 +5,7 @@
 //! ## Supported Color Format
 //!
 //! * [Named colors](https://www.w3.org/TR/css-color-4/#named-colors)
-//! * RGB hexadecimal (with and without `#` prefix)
+//! * RGB hexadecimal (with `#` prefix, `0x` prefix, or without prefix)
 //!      + Short format `#rgb`
 //!      + Short format with alpha `#rgba`
 //!      + Long format `#rrggbb`
@@ -15,7 +15,12 @@
 //! * `hwb()`
 //! * `lab()`
 //! * `lch()`
+//! * `color()` with the `srgb`, `srgb-linear`, `display-p3`, `xyz`, `xyz-d65` and `xyz-d50` color spaces. Out of gamut results are clamped.
 //! * `hwba()`, `hsv()`, `hsva()` - not in CSS standard.
+//!      + Like `hsl()`, the saturation and value of `hsv()` must be both percentages or both
+//!        numbers in the range [0..1]. The alpha can be either, independent of the other values.
+//! * Numeric arguments can be [`calc()`](https://www.w3.org/TR/css-values-4/#calc-func) expressions, e.g. `rgb(calc(255 / 2) 0 0)`, with `min()`, `max()` and `clamp()`.
+//! * [Relative color syntax](https://www.w3.org/TR/css-color-5/#relative-colors), e.g. `hsl(from #ff0000 calc(h + 120) s l)`.
 //!
 //! ### Example Color Format
 //!
@@ -94,19 +99,35 @@
 //!
 //! ## Optional Features
 //!
-//! * `lab`: Enables parsing `lab()` and `lch()` color format.
+//! * `lab`: Enables parsing `lab()` and `lch()` color format. Conversion to and from CIE Lab and LCh
+//!   (e.g. [`Color::to_laba()`](struct.Color.html#method.to_laba)) is always available.
+//! * `fast-linear`: Enables `Color::to_linear_rgba_fast()`, a faster approximation of the sRGB to linear RGB conversion.
 //! * `rust-rgb`: Enables converting from [`rgb`](https://crates.io/crates/rgb) crate types into `Color`.
 //! * `cint`: Enables converting [`cint`](https://crates.io/crates/cint) crate types to and from `Color`.
 //! * `serde`: Enables serializing (into HEX string) and deserializing (from any supported string color format) using [`serde`](https://serde.rs/) framework.
+//!   The [`serde_struct`] module serializes to a struct of the float components instead.
 
+mod builder;
 mod color;
+mod easing;
+mod lab;
 mod parser;
 
 #[cfg(feature = "cint")]
 mod cint;
 
-pub use color::Color;
-pub use parser::{parse, ParseColorError};
+#[cfg(feature = "serde")]
+pub mod serde_struct;
+
+pub use builder::ColorBuilder;
+pub use color::{
+    AlphaMode, AlphaStyle, Color, GamutMapMethod, HexOptions, Illuminant, InterpSpace,
+};
+pub use easing::{ease_in, ease_in_out, ease_out};
+pub use parser::{
+    hex, is_valid, parse, parse_argb_hex, parse_colors, parse_gradient_stops, parse_named_map,
+    parse_strict, parse_x11, split_css_value, ParseColorError, ParseColors, ParseNamedMapError,
+};
 
 #[cfg(feature = "named-colors")]
 pub use parser::NAMED_COLORS;
@@ -0,0 +1,372 @@
+// Evaluator for CSS `calc()` expressions, using precedence climbing.
+// https://www.w3.org/TR/css-values-4/#calc-func
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token<'a> {
+    Number(f32),
+    Percentage(f32),
+    // Angle in degrees
+    Angle(f32),
+    Ident(&'a str),
+    Op(char),
+    Open,
+    Close,
+    Comma,
+}
+
+fn tokenize(s: &str) -> Option<Vec<Token<'_>>> {
+    let mut tokens = Vec::new();
+    let bytes = s.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let c = bytes[i] as char;
+
+        if c.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        // A sign is part of the number if it can't be a binary operator here.
+        let signed = (c == '+' || c == '-')
+            && matches!(
+                tokens.last(),
+                None | Some(Token::Op(_) | Token::Open | Token::Comma)
+            )
+            && matches!(bytes.get(i + 1), Some(b) if b.is_ascii_digit() || *b == b'.');
+
+        if c.is_ascii_digit() || c == '.' || signed {
+            let start = i;
+            i += 1;
+            while i < bytes.len() {
+                let b = bytes[i];
+                let exp_sign = (b == b'+' || b == b'-') && matches!(bytes[i - 1], b'e' | b'E');
+                if b.is_ascii_digit() || b == b'.' || b == b'e' || b == b'E' || exp_sign {
+                    i += 1;
+                } else {
+                    break;
+                }
+            }
+            let t: f32 = s[start..i].parse().ok()?;
+            if bytes.get(i) == Some(&b'%') {
+                i += 1;
+                tokens.push(Token::Percentage(t));
+            } else if matches!(bytes.get(i), Some(b) if b.is_ascii_alphabetic()) {
+                let start = i;
+                while i < bytes.len() && bytes[i].is_ascii_alphabetic() {
+                    i += 1;
+                }
+                let t = match s[start..i].to_ascii_lowercase().as_str() {
+                    "deg" => t,
+                    "grad" => t * 360.0 / 400.0,
+                    "rad" => t.to_degrees(),
+                    "turn" => t * 360.0,
+                    _ => return None,
+                };
+                tokens.push(Token::Angle(t));
+            } else {
+                tokens.push(Token::Number(t));
+            }
+            continue;
+        }
+
+        if c.is_ascii_alphabetic() || c == '_' {
+            let start = i;
+            while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                i += 1;
+            }
+            tokens.push(Token::Ident(&s[start..i]));
+            continue;
+        }
+
+        let token = match c {
+            '+' | '-' | '*' | '/' => Token::Op(c),
+            '(' => Token::Open,
+            ')' => Token::Close,
+            ',' => Token::Comma,
+            _ => return None,
+        };
+        tokens.push(token);
+        i += 1;
+    }
+
+    Some(tokens)
+}
+
+// Maximum nesting of unary signs, parentheses and math functions, so that deeply nested input
+// can't overflow the stack.
+const MAX_DEPTH: usize = 32;
+
+struct Parser<'a, 'v> {
+    tokens: Vec<Token<'a>>,
+    pos: usize,
+    depth: usize,
+    vars: &'v [(&'v str, f32)],
+    percent_ref: Option<f32>,
+    angles: bool,
+}
+
+impl<'a> Parser<'a, '_> {
+    fn peek(&self) -> Option<Token<'a>> {
+        self.tokens.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        let t = self.peek();
+        self.pos += 1;
+        t
+    }
+
+    // expr := term (('+' | '-') term)*
+    fn expr(&mut self) -> Option<f32> {
+        let mut value = self.term()?;
+        while let Some(Token::Op(op @ ('+' | '-'))) = self.peek() {
+            self.pos += 1;
+            let rhs = self.term()?;
+            value = if op == '+' { value + rhs } else { value - rhs };
+        }
+        Some(value)
+    }
+
+    // term := factor (('*' | '/') factor)*
+    fn term(&mut self) -> Option<f32> {
+        let mut value = self.factor()?;
+        while let Some(Token::Op(op @ ('*' | '/'))) = self.peek() {
+            self.pos += 1;
+            let rhs = self.factor()?;
+            value = if op == '*' { value * rhs } else { value / rhs };
+        }
+        Some(value)
+    }
+
+    // factor := ('-' | '+') factor | number | percentage | angle | variable | '(' expr ')'
+    //         | function '(' args ')'
+    fn factor(&mut self) -> Option<f32> {
+        if self.depth >= MAX_DEPTH {
+            return None;
+        }
+        self.depth += 1;
+        let value = self.primary();
+        self.depth -= 1;
+        value
+    }
+
+    fn primary(&mut self) -> Option<f32> {
+        match self.next()? {
+            Token::Number(t) => Some(t),
+            Token::Percentage(t) => self.percent_ref.map(|r| t / 100.0 * r),
+            Token::Angle(t) if self.angles => Some(t),
+            Token::Op('-') => self.factor().map(|t| -t),
+            Token::Op('+') => self.factor(),
+            Token::Open => self.group(),
+            Token::Ident(name) if self.peek() == Some(Token::Open) => {
+                self.pos += 1;
+                self.function(name)
+            }
+            Token::Ident(name) => self
+                .vars
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(name))
+                .map(|(_, v)| *v),
+            _ => None,
+        }
+    }
+
+    // Math function call, after the opening parenthesis.
+    fn function(&mut self, name: &str) -> Option<f32> {
+        let mut args = vec![self.expr()?];
+        loop {
+            match self.next()? {
+                Token::Comma => args.push(self.expr()?),
+                Token::Close => break,
+                _ => return None,
+            }
+        }
+        match (name.to_ascii_lowercase().as_str(), args.as_slice()) {
+            ("calc", [t]) => Some(*t),
+            ("min", _) => args.into_iter().reduce(f32::min),
+            ("max", _) => args.into_iter().reduce(f32::max),
+            ("clamp", [min, t, max]) => Some(t.min(*max).max(*min)),
+            _ => None,
+        }
+    }
+
+    // The rest of a parenthesized expression, after the opening parenthesis.
+    fn group(&mut self) -> Option<f32> {
+        let value = self.expr()?;
+        if self.next()? != Token::Close {
+            return None;
+        }
+        Some(value)
+    }
+}
+
+// Evaluate a `calc(...)` expression. Identifiers are looked up in `vars`. A percentage is
+// resolved against `percent_ref` (`50%` is `0.5 * percent_ref`), or is invalid if it is `None`.
+// Angles are invalid.
+pub(crate) fn parse_calc(s: &str, vars: &[(&str, f32)], percent_ref: Option<f32>) -> Option<f32> {
+    eval(s, vars, percent_ref, false)
+}
+
+// Evaluate a `calc(...)` expression for a hue, in degrees. Angles (`deg`, `grad`, `rad` and
+// `turn`) are converted to degrees, and plain numbers are degrees. Percentages are invalid.
+pub(crate) fn parse_calc_angle(s: &str, vars: &[(&str, f32)]) -> Option<f32> {
+    eval(s, vars, None, true)
+}
+
+fn eval(s: &str, vars: &[(&str, f32)], percent_ref: Option<f32>, angles: bool) -> Option<f32> {
+    let s = s.trim();
+    let inner = s.get(..5)?.eq_ignore_ascii_case("calc(").then(|| &s[5..])?;
+    let inner = inner.strip_suffix(')')?;
+
+    let mut parser = Parser {
+        tokens: tokenize(inner)?,
+        pos: 0,
+        depth: 0,
+        vars,
+        percent_ref,
+        angles,
+    };
+    let value = parser.expr()?;
+    if parser.pos != parser.tokens.len() || !value.is_finite() {
+        return None;
+    }
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_calc() {
+        let test_data = [
+            ("calc(1)", Some(1.0)),
+            ("calc( -1.5 )", Some(-1.5)),
+            ("calc(1 + 2)", Some(3.0)),
+            ("calc(1 + 2 * 3)", Some(7.0)),
+            ("calc(1 * 2 + 3)", Some(5.0)),
+            ("calc(10 - 4 / 2)", Some(8.0)),
+            ("calc(10 - 4 - 3)", Some(3.0)),
+            ("calc(24 / 4 / 2)", Some(3.0)),
+            ("calc(2 * 3 + 4 * 5)", Some(26.0)),
+            ("calc(1 - 2 * 3 + 4)", Some(-1.0)),
+            ("calc((1 + 2) * 3)", Some(9.0)),
+            ("calc(2 * (3 + 4) - 1)", Some(13.0)),
+            ("calc(((1)))", Some(1.0)),
+            ("calc(1 - -2)", Some(3.0)),
+            ("calc(-2 * -3)", Some(6.0)),
+            ("calc(1e2 / 4)", Some(25.0)),
+            ("calc(1e-1 * 10)", Some(1.0)),
+            ("calc(.5 + 5.)", Some(5.5)),
+            ("calc(2*3)", Some(6.0)),
+            ("calc(calc(1 + 1) * 2)", Some(4.0)),
+            ("CALC(1 + 1)", Some(2.0)),
+            ("calc(-(1 + 2))", Some(-3.0)),
+            ("calc(2 * -(1 + 2))", Some(-6.0)),
+            ("calc(-(-(2)))", Some(2.0)),
+            ("calc(+(1))", Some(1.0)),
+            ("calc(min(3, 1, 2))", Some(1.0)),
+            ("calc(max(3, 1, 2) * 2)", Some(6.0)),
+            ("calc(min(5))", Some(5.0)),
+            ("calc(clamp(0, 5, 1))", Some(1.0)),
+            ("calc(clamp(0, -5, 1))", Some(0.0)),
+            ("calc(clamp(0, 0.5, 1))", Some(0.5)),
+            ("calc(-min(1, 2) + max(1 + 1, 3 * 2))", Some(5.0)),
+            ("calc(min(1, max(2, 3)))", Some(1.0)),
+            ("calc(min())", None),
+            ("calc(min(1,))", None),
+            ("calc(clamp(0, 1))", None),
+            ("calc(calc(1, 2))", None),
+            ("calc(foo(1))", None),
+            ("calc(1, 2)", None),
+            ("calc()", None),
+            ("calc(1 +)", None),
+            ("calc(* 1)", None),
+            ("calc(1 2)", None),
+            ("calc((1 + 2)", None),
+            ("calc(1 + 2))", None),
+            ("calc(1 / 0)", None),
+            ("calc(x)", None),
+            ("calc(1 % 2)", None),
+            ("calc 1", None),
+            ("1 + 2", None),
+        ];
+        for (s, expected) in test_data {
+            assert_eq!(parse_calc(s, &[], None), expected, "{}", s);
+        }
+
+        // Nesting depth is limited
+        let nested = |open: &str, close: &str, n: usize| {
+            format!("calc({}1{})", open.repeat(n), close.repeat(n))
+        };
+        assert_eq!(parse_calc(&nested("(", ")", 20), &[], None), Some(1.0));
+        assert_eq!(parse_calc(&nested("-", "", 20), &[], None), Some(1.0));
+        assert_eq!(parse_calc(&nested("min(", ")", 20), &[], None), Some(1.0));
+        assert_eq!(parse_calc(&nested("(", ")", 100), &[], None), None);
+        assert_eq!(parse_calc(&nested("-", "", 100_000), &[], None), None);
+        assert_eq!(parse_calc(&nested("calc(", ")", 100_000), &[], None), None);
+    }
+
+    #[test]
+    fn test_parse_calc_vars() {
+        let vars = [("r", 0.5), ("g", 0.25), ("alpha", 1.0)];
+        let test_data = [
+            ("calc(r)", Some(0.5)),
+            ("calc(r + g * 2)", Some(1.0)),
+            ("calc(alpha / 2)", Some(0.5)),
+            ("calc(R * 2)", Some(1.0)),
+            ("calc(-(r))", Some(-0.5)),
+            ("calc(clamp(0, r * 4, 1))", Some(1.0)),
+            ("calc(max(r, g))", Some(0.5)),
+            ("calc(b)", None),
+        ];
+        for (s, expected) in test_data {
+            assert_eq!(parse_calc(s, &vars, None), expected, "{}", s);
+        }
+    }
+
+    #[test]
+    fn test_parse_calc_percentage() {
+        let vars = [("r", 100.0)];
+        let test_data = [
+            ("calc(50%)", Some(127.5)),
+            ("calc(r * 50%)", Some(12750.0)),
+            ("calc(r + 10%)", Some(125.5)),
+            ("calc(100% - r)", Some(155.0)),
+            ("calc(-50%)", Some(-127.5)),
+            ("calc(min(r, 20%))", Some(51.0)),
+            ("calc(1e2%)", Some(255.0)),
+        ];
+        for (s, expected) in test_data {
+            assert_eq!(parse_calc(s, &vars, Some(255.0)), expected, "{}", s);
+        }
+
+        assert_eq!(parse_calc("calc(50%)", &vars, None), None);
+        assert_eq!(parse_calc("calc(r %)", &vars, Some(255.0)), None);
+    }
+
+    #[test]
+    fn test_parse_calc_angle() {
+        let vars = [("h", 90.0)];
+        let test_data = [
+            ("calc(h)", Some(90.0)),
+            ("calc(h + 10)", Some(100.0)),
+            ("calc(h + 10deg)", Some(100.0)),
+            ("calc(h + 0.5turn)", Some(270.0)),
+            ("calc(h - 100grad)", Some(0.0)),
+            ("calc(1TURN / 4)", Some(90.0)),
+            ("calc(-0.25turn)", Some(-90.0)),
+            ("calc(max(1rad, 30deg))", Some(1f32.to_degrees())),
+            ("calc(h + 50%)", None),
+            ("calc(1foo)", None),
+            ("calc(1 deg)", None),
+        ];
+        for (s, expected) in test_data {
+            assert_eq!(parse_calc_angle(s, &vars), expected, "{}", s);
+        }
+
+        // Angles are only valid for a hue
+        assert_eq!(parse_calc("calc(1turn)", &vars, Some(1.0)), None);
+    }
+}
@@ -0,0 +1,52 @@
+use super::split::{split_by_comma, SplitByComma};
+use crate::{parse, Color, ParseColorError};
+
+/// Iterator over the colors in a comma separated list, created by [`parse_colors`].
+///
+/// Commas inside color functions do not split, so `rgb(255, 0, 0), blue` yields two colors.
+pub struct ParseColors<'a> {
+    parts: SplitByComma<'a>,
+}
+
+impl Iterator for ParseColors<'_> {
+    type Item = Result<Color, ParseColorError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.parts.next().map(parse)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.parts.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for ParseColors<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.parts.next_back().map(parse)
+    }
+}
+
+/// Parse a comma separated list of CSS colors.
+///
+/// Each item is parsed with [`parse()`]. An empty (or all whitespace) string yields no items.
+///
+/// # Examples
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let colors = csscolorparser::parse_colors("#f00, rgb(0, 255, 0), hsl(240 100% 50%)")
+///     .collect::<Result<Vec<_>, _>>()?;
+///
+/// assert_eq!(colors.len(), 3);
+/// assert_eq!(colors[1].to_rgba8(), [0, 255, 0, 255]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn parse_colors(s: &str) -> ParseColors<'_> {
+    let parts = if s.trim().is_empty() {
+        SplitByComma::default()
+    } else {
+        split_by_comma(s)
+    };
+    ParseColors { parts }
+}
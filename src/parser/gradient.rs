@@ -0,0 +1,96 @@
+use super::split::{split_by_comma, split_by_space};
+use super::{parse, parse_percent_or_float, ParseColorError};
+use crate::Color;
+
+/// Extract the color stops from a CSS gradient.
+///
+/// Accepts a full gradient function (`linear-gradient()`, `radial-gradient()`,
+/// `conic-gradient()` and their `repeating-` variants) or just its argument list. The leading
+/// direction or shape argument (e.g. `to right`, `45deg`, `circle at center`) and color hints
+/// are ignored.
+///
+/// Each stop is returned with its position as a fraction, if the position is a percentage. A
+/// stop with two positions yields two stops. Positions in other units are returned as `None`.
+///
+/// # Examples
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let stops = csscolorparser::parse_gradient_stops("linear-gradient(to right, #f00, #00f 75%)")?;
+///
+/// assert_eq!(stops.len(), 2);
+/// assert_eq!(stops[0].0.to_hex_string(), "#ff0000");
+/// assert_eq!(stops[0].1, None);
+/// assert_eq!(stops[1].0.to_hex_string(), "#0000ff");
+/// assert_eq!(stops[1].1, Some(0.75));
+/// # Ok(())
+/// # }
+/// ```
+pub fn parse_gradient_stops(s: &str) -> Result<Vec<(Color, Option<f32>)>, ParseColorError> {
+    let s = s.trim();
+    let args = match (s.find('('), s.strip_suffix(')')) {
+        (Some(i), Some(inner)) if s[..i].trim_end().to_lowercase().ends_with("gradient") => {
+            &inner[i + 1..]
+        }
+        _ => s,
+    };
+
+    let mut stops = Vec::new();
+
+    for (i, arg) in split_by_comma(args).enumerate() {
+        let mut tokens = split_by_space(arg);
+
+        let color = match tokens.next() {
+            Some(token) => match parse(token) {
+                Ok(c) => c,
+                // Direction, shape or color hint
+                Err(_) if i == 0 && is_direction_or_shape(token) => continue,
+                Err(_) if arg == token && is_position(token) => continue,
+                Err(err) => return Err(err),
+            },
+            None => return Err(ParseColorError::InvalidUnknown),
+        };
+
+        let positions = tokens.collect::<Vec<_>>();
+        if positions.len() > 2 || !positions.iter().all(|t| is_position(t)) {
+            return Err(ParseColorError::InvalidUnknown);
+        }
+
+        if positions.is_empty() {
+            stops.push((color, None));
+            continue;
+        }
+
+        for pos in positions {
+            stops.push((color.clone(), parse_position(pos)));
+        }
+    }
+
+    Ok(stops)
+}
+
+// The first token of a leading `to right`, `45deg`, `circle at center` or `from 90deg` argument.
+fn is_direction_or_shape(s: &str) -> bool {
+    let s = s.to_ascii_lowercase();
+    match s.as_str() {
+        "to" | "at" | "from" | "circle" | "ellipse" => true,
+        _ => {
+            is_position(&s)
+                && ["deg", "grad", "rad", "turn"]
+                    .iter()
+                    .any(|unit| s.ends_with(unit))
+        }
+    }
+}
+
+fn is_position(s: &str) -> bool {
+    s.starts_with(|c: char| c.is_ascii_digit() || c == '.' || c == '-' || c == '+')
+        || s.starts_with("calc(")
+}
+
+fn parse_position(s: &str) -> Option<f32> {
+    match parse_percent_or_float(s) {
+        Some((t, true)) => Some(t),
+        _ => None,
+    }
+}
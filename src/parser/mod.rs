@@ -1,10 +1,27 @@
 use std::{error, fmt};
 
-use crate::Color;
+use crate::{lab, Color, Illuminant};
+
+use calc::{parse_calc, parse_calc_angle};
+use split::{arg_separators, split_args, split_by_comma};
+
+mod calc;
+mod colors;
+mod gradient;
+mod named_map;
+mod relative;
+mod split;
+mod x11;
 
 #[cfg(feature = "named-colors")]
 mod named_colors;
 
+pub use colors::{parse_colors, ParseColors};
+pub use gradient::parse_gradient_stops;
+pub use named_map::{parse_named_map, ParseNamedMapError};
+pub use split::split_css_value;
+pub use x11::parse_x11;
+
 #[cfg(feature = "named-colors")]
 pub use named_colors::NAMED_COLORS;
 
@@ -21,6 +38,9 @@ pub enum ParseColorError {
     InvalidLch,
     InvalidOklab,
     InvalidOklch,
+    InvalidColorFunction,
+    /// Mixed or misplaced argument separators, only returned by [`parse_strict()`].
+    InvalidSeparators,
     InvalidFunction,
     InvalidUnknown,
 }
@@ -39,6 +59,8 @@ impl fmt::Display for ParseColorError {
             Self::InvalidLch => f.write_str("invalid lch format"),
             Self::InvalidOklab => f.write_str("invalid oklab format"),
             Self::InvalidOklch => f.write_str("invalid oklch format"),
+            Self::InvalidColorFunction => f.write_str("invalid color() format"),
+            Self::InvalidSeparators => f.write_str("invalid or mixed argument separators"),
             Self::InvalidFunction => f.write_str("invalid color function"),
             Self::InvalidUnknown => f.write_str("invalid unknown format"),
         }
@@ -49,6 +71,12 @@ impl error::Error for ParseColorError {}
 
 /// Parse CSS color string
 ///
+/// A `none` value, a missing component in CSS Color 4, is treated as 0. It is accepted for any
+/// component of the color functions, e.g. `rgb(none 128 0)`, `hsl(none 100% 50%)`,
+/// `oklch(0.5 0.1 none)` or `rgb(255 0 0 / none)`. A `none` can be used together with both
+/// numbers and percentages where those can't be mixed, e.g. `hsl(0 none 50%)`. The parsed
+/// [`Color`] does not keep track of which components were missing.
+///
 /// # Examples
 ///
 /// ```
@@ -77,6 +105,55 @@ impl error::Error for ParseColorError {}
 /// # }
 /// ```
 pub fn parse(s: &str) -> Result<Color, ParseColorError> {
+    parse_with(s, false)
+}
+
+/// Parse CSS color string, following the CSS syntax more strictly than [`parse()`].
+///
+/// In addition to the rules of [`parse()`]:
+///
+/// * The separators must not be mixed: either all arguments are separated by commas (the
+///   legacy syntax), or the channels are separated by whitespace and the alpha by a slash, as
+///   in `rgb(255 0 0 / 50%)`. So `rgb(255, 0, 0 / 50%)`, `rgb(255 0 0 50%)` and
+///   `rgb(255 0 0,)` are invalid, with the error [`ParseColorError::InvalidSeparators`].
+///   Relative colors, e.g. `rgb(from red r g b)`, only allow the whitespace syntax.
+/// * With the legacy syntax, `rgb()` and `hsl()` must have exactly 3 arguments, and `rgba()`
+///   and `hsla()` exactly 4.
+///
+/// # Examples
+///
+/// ```
+/// use csscolorparser::{parse, parse_strict};
+///
+/// assert!(parse_strict("rgba(0, 0, 0, 0.5)").is_ok());
+/// assert!(parse_strict("rgb(0, 0, 0, 0.5)").is_err());
+/// assert!(parse("rgb(0, 0, 0, 0.5)").is_ok());
+/// ```
+pub fn parse_strict(s: &str) -> Result<Color, ParseColorError> {
+    parse_with(s, true)
+}
+
+/// Returns `true` if `s` is a valid color string, that is [`parse()`] would succeed. Equivalent
+/// to `parse(s).is_ok()`, meant for input validation.
+///
+/// # Examples
+/// ```
+/// use csscolorparser::is_valid;
+///
+/// assert!(is_valid("rgb(255 0 0 / 50%)"));
+/// assert!(!is_valid("rgb(255 0)"));
+/// ```
+pub fn is_valid(s: &str) -> bool {
+    parse(s).is_ok()
+}
+
+fn parse_with(s: &str, strict: bool) -> Result<Color, ParseColorError> {
+    // Named colors, without allocating
+    #[cfg(feature = "named-colors")]
+    if let Some(c) = parse_named(s.trim()) {
+        return Ok(c);
+    }
+
     let s = s.trim().to_lowercase();
 
     if s == "transparent" {
@@ -94,10 +171,31 @@ pub fn parse(s: &str) -> Result<Color, ParseColorError> {
         return parse_hex(s);
     }
 
+    // Hex format with prefix '0x', as used for integer literals. Always parsed as hex.
+    if let Some(s) = s.strip_prefix("0x") {
+        return parse_hex(s);
+    }
+
     if let (Some(i), Some(s)) = (s.find('('), s.strip_suffix(')')) {
         let fname = &s[..i].trim_end();
-        let s = &s[i + 1..].replace([',', '/'], " ");
-        let params = s.split_whitespace().collect::<Vec<&str>>();
+        let s = &s[i + 1..];
+
+        if has_empty_arg(s) {
+            return Err(function_error(fname));
+        }
+
+        let params = split_args(s).collect::<Vec<&str>>();
+
+        let relative = params.first() == Some(&"from");
+
+        if strict {
+            check_strict(fname, s, params.len(), relative)?;
+        }
+
+        if relative {
+            return relative::parse_relative(fname, &params[1..]);
+        }
+
         let p_len = params.len();
 
         match *fname {
@@ -106,27 +204,21 @@ pub fn parse(s: &str) -> Result<Color, ParseColorError> {
                     return Err(ParseColorError::InvalidRgb);
                 }
 
-                let r = parse_percent_or_255(params[0]);
-                let g = parse_percent_or_255(params[1]);
-                let b = parse_percent_or_255(params[2]);
+                let rgb = parse_rgb_channels(&params[0..3]);
 
                 let a = if p_len == 4 {
-                    parse_percent_or_float(params[3])
+                    parse_alpha(params[3])
                 } else {
-                    Some((1.0, true))
+                    Some(1.0)
                 };
 
-                if let (Some((r, r_fmt)), Some((g, g_fmt)), Some((b, b_fmt)), Some((a, _))) =
-                    (r, g, b, a)
-                {
-                    if r_fmt == g_fmt && g_fmt == b_fmt {
-                        return Ok(Color {
-                            r: r.clamp(0.0, 1.0),
-                            g: g.clamp(0.0, 1.0),
-                            b: b.clamp(0.0, 1.0),
-                            a: a.clamp(0.0, 1.0),
-                        });
-                    }
+                if let (Some([r, g, b]), Some(a)) = (rgb, a) {
+                    return Ok(Color {
+                        r: r.clamp(0.0, 1.0),
+                        g: g.clamp(0.0, 1.0),
+                        b: b.clamp(0.0, 1.0),
+                        a: a.clamp(0.0, 1.0),
+                    });
                 }
 
                 return Err(ParseColorError::InvalidRgb);
@@ -141,13 +233,13 @@ pub fn parse(s: &str) -> Result<Color, ParseColorError> {
                 let l = parse_percent_or_float(params[2]);
 
                 let a = if p_len == 4 {
-                    parse_percent_or_float(params[3])
+                    parse_alpha(params[3])
                 } else {
-                    Some((1.0, true))
+                    Some(1.0)
                 };
 
-                if let (Some(h), Some((s, s_fmt)), Some((l, l_fmt)), Some((a, _))) = (h, s, l, a) {
-                    if s_fmt == l_fmt {
+                if let (Some(h), Some((s, s_fmt)), Some((l, l_fmt)), Some(a)) = (h, s, l, a) {
+                    if same_format(params[1], s_fmt, params[2], l_fmt) {
                         return Ok(Color::from_hsla(h, s, l, a));
                     }
                 }
@@ -164,13 +256,13 @@ pub fn parse(s: &str) -> Result<Color, ParseColorError> {
                 let b = parse_percent_or_float(params[2]);
 
                 let a = if p_len == 4 {
-                    parse_percent_or_float(params[3])
+                    parse_alpha(params[3])
                 } else {
-                    Some((1.0, true))
+                    Some(1.0)
                 };
 
-                if let (Some(h), Some((w, w_fmt)), Some((b, b_fmt)), Some((a, _))) = (h, w, b, a) {
-                    if w_fmt == b_fmt {
+                if let (Some(h), Some((w, w_fmt)), Some((b, b_fmt)), Some(a)) = (h, w, b, a) {
+                    if same_format(params[1], w_fmt, params[2], b_fmt) {
                         return Ok(Color::from_hwba(h, w, b, a));
                     }
                 }
@@ -187,13 +279,13 @@ pub fn parse(s: &str) -> Result<Color, ParseColorError> {
                 let v = parse_percent_or_float(params[2]);
 
                 let a = if p_len == 4 {
-                    parse_percent_or_float(params[3])
+                    parse_alpha(params[3])
                 } else {
-                    Some((1.0, true))
+                    Some(1.0)
                 };
 
-                if let (Some(h), Some((s, s_fmt)), Some((v, v_fmt)), Some((a, _))) = (h, s, v, a) {
-                    if s_fmt == v_fmt {
+                if let (Some(h), Some((s, s_fmt)), Some((v, v_fmt)), Some(a)) = (h, s, v, a) {
+                    if same_format(params[1], s_fmt, params[2], v_fmt) {
                         return Ok(Color::from_hsva(h, s, v, a));
                     }
                 }
@@ -211,12 +303,12 @@ pub fn parse(s: &str) -> Result<Color, ParseColorError> {
                 let b = parse_percent_or_float(params[2]);
 
                 let alpha = if p_len == 4 {
-                    parse_percent_or_float(params[3])
+                    parse_alpha(params[3])
                 } else {
-                    Some((1.0, true))
+                    Some(1.0)
                 };
 
-                if let (Some((l, l_fmt)), Some((a, a_fmt)), Some((b, b_fmt)), Some((alpha, _))) =
+                if let (Some((l, l_fmt)), Some((a, a_fmt)), Some((b, b_fmt)), Some(alpha)) =
                     (l, a, b, alpha)
                 {
                     let l = if l_fmt { l * 100.0 } else { l };
@@ -230,7 +322,7 @@ pub fn parse(s: &str) -> Result<Color, ParseColorError> {
                     } else {
                         b
                     };
-                    return Ok(Color::from_laba(l.max(0.0), a, b, alpha));
+                    return Ok(Color::from_laba(l.clamp(0.0, 100.0), a, b, alpha));
                 }
 
                 return Err(ParseColorError::InvalidLab);
@@ -246,18 +338,17 @@ pub fn parse(s: &str) -> Result<Color, ParseColorError> {
                 let h = parse_angle(params[2]);
 
                 let alpha = if p_len == 4 {
-                    parse_percent_or_float(params[3])
+                    parse_alpha(params[3])
                 } else {
-                    Some((1.0, true))
+                    Some(1.0)
                 };
 
-                if let (Some((l, l_fmt)), Some((c, c_fmt)), Some(h), Some((alpha, _))) =
-                    (l, c, h, alpha)
+                if let (Some((l, l_fmt)), Some((c, c_fmt)), Some(h), Some(alpha)) = (l, c, h, alpha)
                 {
                     let l = if l_fmt { l * 100.0 } else { l };
                     let c = if c_fmt { c * 150.0 } else { c };
                     return Ok(Color::from_lcha(
-                        l.max(0.0),
+                        l.clamp(0.0, 100.0),
                         c.max(0.0),
                         h.to_radians(),
                         alpha,
@@ -276,12 +367,12 @@ pub fn parse(s: &str) -> Result<Color, ParseColorError> {
                 let b = parse_percent_or_float(params[2]);
 
                 let alpha = if p_len == 4 {
-                    parse_percent_or_float(params[3])
+                    parse_alpha(params[3])
                 } else {
-                    Some((1.0, true))
+                    Some(1.0)
                 };
 
-                if let (Some((l, _)), Some((a, a_fmt)), Some((b, b_fmt)), Some((alpha, _))) =
+                if let (Some((l, _)), Some((a, a_fmt)), Some((b, b_fmt)), Some(alpha)) =
                     (l, a, b, alpha)
                 {
                     let a = if a_fmt {
@@ -294,7 +385,7 @@ pub fn parse(s: &str) -> Result<Color, ParseColorError> {
                     } else {
                         b
                     };
-                    return Ok(Color::from_oklaba(l.max(0.0), a, b, alpha));
+                    return Ok(Color::from_oklaba(l.clamp(0.0, 1.0), a, b, alpha));
                 }
 
                 return Err(ParseColorError::InvalidOklab);
@@ -309,17 +400,15 @@ pub fn parse(s: &str) -> Result<Color, ParseColorError> {
                 let h = parse_angle(params[2]);
 
                 let alpha = if p_len == 4 {
-                    parse_percent_or_float(params[3])
+                    parse_alpha(params[3])
                 } else {
-                    Some((1.0, true))
+                    Some(1.0)
                 };
 
-                if let (Some((l, _)), Some((c, c_fmt)), Some(h), Some((alpha, _))) =
-                    (l, c, h, alpha)
-                {
+                if let (Some((l, _)), Some((c, c_fmt)), Some(h), Some(alpha)) = (l, c, h, alpha) {
                     let c = if c_fmt { c * 0.4 } else { c };
                     return Ok(Color::from_oklcha(
-                        l.max(0.0),
+                        l.clamp(0.0, 1.0),
                         c.max(0.0),
                         h.to_radians(),
                         alpha,
@@ -328,6 +417,44 @@ pub fn parse(s: &str) -> Result<Color, ParseColorError> {
 
                 return Err(ParseColorError::InvalidOklch);
             }
+            "color" => {
+                if p_len != 4 && p_len != 5 {
+                    return Err(ParseColorError::InvalidColorFunction);
+                }
+
+                // The channels follow the same rules as the alpha: numbers, or percentages of 1.
+                let c0 = parse_alpha(params[1]);
+                let c1 = parse_alpha(params[2]);
+                let c2 = parse_alpha(params[3]);
+
+                let alpha = if p_len == 5 {
+                    parse_alpha(params[4])
+                } else {
+                    Some(1.0)
+                };
+
+                if let (Some(c0), Some(c1), Some(c2), Some(alpha)) = (c0, c1, c2, alpha) {
+                    let c = match params[0] {
+                        "srgb" => Some(Color::new(c0, c1, c2, alpha)),
+                        "srgb-linear" => Some(Color::from_linear_rgba(c0, c1, c2, alpha)),
+                        "display-p3" => {
+                            // Same transfer function as sRGB
+                            let [r, g, b, _] = Color::new(c0, c1, c2, alpha).to_linear_rgba();
+                            let [r, g, b] = lab::linear_p3_to_linear_rgb([r, g, b]);
+                            Some(Color::from_linear_rgba(r, g, b, alpha))
+                        }
+                        "xyz" | "xyz-d65" => Some(Color::from_xyz([c0, c1, c2], Illuminant::D65)),
+                        "xyz-d50" => Some(Color::from_xyz([c0, c1, c2], Illuminant::D50)),
+                        _ => None,
+                    };
+                    if let Some(c) = c {
+                        // from_xyz() is always opaque
+                        return Ok(Color { a: alpha, ..c }.clamp());
+                    }
+                }
+
+                return Err(ParseColorError::InvalidColorFunction);
+            }
             _ => {
                 return Err(ParseColorError::InvalidFunction);
             }
@@ -342,6 +469,72 @@ pub fn parse(s: &str) -> Result<Color, ParseColorError> {
     Err(ParseColorError::InvalidUnknown)
 }
 
+/// Parse a hexadecimal color string, panicking if it is invalid.
+///
+/// The `#` (or `0x`) prefix is optional. Meant for literals in tests and constants, use
+/// [`parse()`] for any input that isn't known to be valid.
+///
+/// # Panics
+///
+/// Panics if `s` is not a valid hexadecimal color.
+///
+/// # Examples
+/// ```
+/// use csscolorparser::hex;
+///
+/// assert_eq!(hex("#ff0000").to_rgba8(), [255, 0, 0, 255]);
+/// assert_eq!(hex("0f08").to_rgba8(), [0, 255, 0, 136]);
+/// ```
+#[track_caller]
+pub fn hex(s: &str) -> Color {
+    let t = s.trim();
+    let t = t
+        .strip_prefix('#')
+        .or_else(|| t.strip_prefix("0x"))
+        .or_else(|| t.strip_prefix("0X"))
+        .unwrap_or(t);
+    match parse_hex(t) {
+        Ok(c) => c,
+        Err(_) => panic!("invalid hex color: {:?}", s),
+    }
+}
+
+/// Parse a hexadecimal color string in ARGB order, with the alpha first: `#aarrggbb` or `#argb`,
+/// as used by Android and some game engines.
+///
+/// This is **not** the CSS order, which has the alpha last (`#rrggbbaa`, see [`parse()`]). The
+/// forms without alpha, `#rrggbb` and `#rgb`, are the same in both orders. The `#` (or `0x`)
+/// prefix is optional.
+///
+/// # Examples
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use csscolorparser::parse_argb_hex;
+///
+/// assert_eq!(parse_argb_hex("#80ff0000")?.to_rgba8(), [255, 0, 0, 128]);
+/// assert_eq!(parse_argb_hex("0xff00ff00")?.to_rgba8(), [0, 255, 0, 255]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn parse_argb_hex(s: &str) -> Result<Color, ParseColorError> {
+    let s = s.trim();
+    let s = s
+        .strip_prefix('#')
+        .or_else(|| s.strip_prefix("0x"))
+        .or_else(|| s.strip_prefix("0X"))
+        .unwrap_or(s);
+    if !s.is_ascii() {
+        return Err(ParseColorError::InvalidHex);
+    }
+    // Move the alpha to the end
+    match s.len() {
+        4 => parse_hex(&format!("{}{}", &s[1..], &s[..1])),
+        8 => parse_hex(&format!("{}{}", &s[2..], &s[..2])),
+        _ => parse_hex(s),
+    }
+}
+
 fn parse_hex(s: &str) -> Result<Color, ParseColorError> {
     if !s.is_ascii() {
         return Err(ParseColorError::InvalidHex);
@@ -384,37 +577,187 @@ fn parse_hex(s: &str) -> Result<Color, ParseColorError> {
     }
 }
 
+// Case-insensitive lookup of a named color or `transparent`, lowercasing into a stack buffer.
+// Returns `None` for anything else, which is left to the general parser.
+#[cfg(feature = "named-colors")]
+pub(crate) fn parse_named(s: &str) -> Option<Color> {
+    // The longest name is `lightgoldenrodyellow`.
+    let mut buf = [0u8; 20];
+    let bytes = s.as_bytes();
+    if bytes.len() > buf.len() || !bytes.iter().all(u8::is_ascii_alphabetic) {
+        return None;
+    }
+    let buf = &mut buf[..bytes.len()];
+    buf.copy_from_slice(bytes);
+    buf.make_ascii_lowercase();
+    let name = std::str::from_utf8(buf).ok()?;
+
+    if name == "transparent" {
+        return Some(Color::new(0.0, 0.0, 0.0, 0.0));
+    }
+    NAMED_COLORS
+        .get(name)
+        .map(|&[r, g, b]| Color::from_rgba8(r, g, b, 255))
+}
+
+// The error for an invalid argument list of the color function `fname`.
+fn function_error(fname: &str) -> ParseColorError {
+    match fname {
+        "rgb" | "rgba" => ParseColorError::InvalidRgb,
+        "hsl" | "hsla" => ParseColorError::InvalidHsl,
+        "hwb" | "hwba" => ParseColorError::InvalidHwb,
+        "hsv" | "hsva" => ParseColorError::InvalidHsv,
+        #[cfg(feature = "lab")]
+        "lab" => ParseColorError::InvalidLab,
+        #[cfg(feature = "lab")]
+        "lch" => ParseColorError::InvalidLch,
+        "oklab" => ParseColorError::InvalidOklab,
+        "oklch" => ParseColorError::InvalidOklch,
+        "color" => ParseColorError::InvalidColorFunction,
+        _ => ParseColorError::InvalidFunction,
+    }
+}
+
+// Syntax rules of `parse_strict()` for the arguments `args` of the color function `fname`.
+// `relative` is set for the relative color syntax, where the arguments start with `from`.
+fn check_strict(
+    fname: &str,
+    args: &str,
+    n_args: usize,
+    relative: bool,
+) -> Result<(), ParseColorError> {
+    let seps = arg_separators(args);
+    let trailing_comma = args.trim_end().ends_with(',');
+
+    if seps.contains(&",") && !relative {
+        // Commas only
+        if !seps.iter().all(|&s| s == ",") {
+            return Err(ParseColorError::InvalidSeparators);
+        }
+        let count_ok = match fname {
+            "rgb" | "hsl" => n_args == 3,
+            "rgba" | "hsla" => n_args == 4,
+            _ => true,
+        };
+        if !count_ok {
+            return Err(function_error(fname));
+        }
+        return Ok(());
+    }
+
+    // Whitespace, and a slash before the alpha. `color()` has the color space first, and
+    // relative colors have `from` and the origin color first.
+    let mut n_channels = if fname == "color" { 4 } else { 3 };
+    if relative {
+        n_channels += 2;
+    }
+    let valid = !trailing_comma
+        && seps
+            .iter()
+            .enumerate()
+            .all(|(i, &s)| s == if i < n_channels - 1 { "" } else { "/" });
+    if !valid {
+        return Err(ParseColorError::InvalidSeparators);
+    }
+    Ok(())
+}
+
+// Whether a comma separated argument list has an empty argument, e.g. `1,,2`. A single
+// trailing comma is allowed.
+fn has_empty_arg(s: &str) -> bool {
+    let s = s.trim_end();
+    let s = s.strip_suffix(',').unwrap_or(s);
+    s.contains(',') && split_by_comma(s).any(str::is_empty)
+}
+
+// A finite number, or a `calc()` expression evaluating to a number. `NaN`, `inf` and out of
+// range values are rejected.
+fn parse_number(s: &str) -> Option<f32> {
+    if s.starts_with("calc(") {
+        return parse_calc(s, &[], None);
+    }
+    s.parse().ok().filter(|t: &f32| t.is_finite())
+}
+
+// Number or percentage, as a fraction. A missing (`none`) value is treated as 0, see
+// `same_format()`.
 fn parse_percent_or_float(s: &str) -> Option<(f32, bool)> {
+    if s == "none" {
+        return Some((0.0, false));
+    }
     s.strip_suffix('%')
-        .and_then(|s| s.parse().ok().map(|t: f32| (t / 100.0, true)))
-        .or_else(|| s.parse().ok().map(|t| (t, false)))
+        .and_then(|s| parse_number(s).map(|t| (t / 100.0, true)))
+        .or_else(|| parse_number(s).map(|t| (t, false)))
+}
+
+// Alpha value: number, percentage, `calc()` or `none`. A missing (`none`) alpha is treated as
+// 0. Percentages inside `calc()` are relative to 1.
+fn parse_alpha(s: &str) -> Option<f32> {
+    if s == "none" {
+        return Some(0.0);
+    }
+    if s.starts_with("calc(") {
+        return parse_calc(s, &[], Some(1.0));
+    }
+    parse_percent_or_float(s).map(|(t, _)| t)
+}
+
+// Whether the arguments `s1` and `s2`, parsed by `parse_percent_or_float()` as percentages or
+// not (`fmt1`, `fmt2`), have the same format. A missing (`none`) value matches either.
+fn same_format(s1: &str, fmt1: bool, s2: &str, fmt2: bool) -> bool {
+    s1 == "none" || s2 == "none" || fmt1 == fmt2
 }
 
 fn parse_percent_or_255(s: &str) -> Option<(f32, bool)> {
     s.strip_suffix('%')
-        .and_then(|s| s.parse().ok().map(|t: f32| (t / 100.0, true)))
-        .or_else(|| s.parse().ok().map(|t: f32| (t / 255.0, false)))
+        .and_then(|s| parse_number(s).map(|t| (t / 100.0, true)))
+        .or_else(|| parse_number(s).map(|t| (t / 255.0, false)))
+}
+
+// RGB channels: all numbers or all percentages. A missing (`none`) channel is treated as 0
+// and can be mixed with either.
+fn parse_rgb_channels(params: &[&str]) -> Option<[f32; 3]> {
+    let mut rgb = [0.0; 3];
+    let mut fmt = None;
+    for (i, s) in params.iter().enumerate() {
+        if *s == "none" {
+            continue;
+        }
+        let (t, is_percent) = parse_percent_or_255(s)?;
+        if *fmt.get_or_insert(is_percent) != is_percent {
+            return None;
+        }
+        rgb[i] = t;
+    }
+    Some(rgb)
 }
 
+// Hue angle in degrees. A missing (`none`) hue is treated as 0.
 fn parse_angle(s: &str) -> Option<f32> {
+    if s == "none" {
+        return Some(0.0);
+    }
+    if s.starts_with("calc(") {
+        return parse_calc_angle(s, &[]);
+    }
     s.strip_suffix("deg")
-        .and_then(|s| s.parse().ok())
+        .and_then(parse_number)
         .or_else(|| {
             s.strip_suffix("grad")
-                .and_then(|s| s.parse().ok())
-                .map(|t: f32| t * 360.0 / 400.0)
+                .and_then(parse_number)
+                .map(|t| t * 360.0 / 400.0)
         })
         .or_else(|| {
             s.strip_suffix("rad")
-                .and_then(|s| s.parse().ok())
-                .map(|t: f32| t.to_degrees())
+                .and_then(parse_number)
+                .map(|t| t.to_degrees())
         })
         .or_else(|| {
             s.strip_suffix("turn")
-                .and_then(|s| s.parse().ok())
-                .map(|t: f32| t * 360.0)
+                .and_then(parse_number)
+                .map(|t| t * 360.0)
         })
-        .or_else(|| s.parse().ok())
+        .or_else(|| parse_number(s))
 }
 
 // Map t from range [a, b] to range [c, d]
@@ -426,6 +769,51 @@ fn remap(t: f32, a: f32, b: f32, c: f32, d: f32) -> f32 {
 mod tests {
     use super::*;
 
+    #[cfg(feature = "named-colors")]
+    #[test]
+    fn test_parse_named() {
+        for (&name, &[r, g, b]) in NAMED_COLORS.entries() {
+            let expected = Some(Color::from_rgba8(r, g, b, 255));
+            assert_eq!(parse_named(name), expected);
+            assert_eq!(parse_named(&name.to_uppercase()), expected);
+        }
+        assert_eq!(
+            parse_named("TransParent"),
+            Some(Color::new(0.0, 0.0, 0.0, 0.0))
+        );
+
+        let test_data = [
+            "",
+            "re",
+            "redd",
+            "red ",
+            "#f00",
+            "lightgoldenrodyellowx",
+            "bl\u{e4}ck",
+        ];
+        for s in test_data {
+            assert_eq!(parse_named(s), None, "{}", s);
+        }
+    }
+
+    #[test]
+    fn test_has_empty_arg() {
+        let test_data = [
+            ("", false),
+            ("255 0 0", false),
+            ("255, 0, 0", false),
+            ("255, 0, 0,", false),
+            ("255, 0, 0 , ", false),
+            ("255,,0", true),
+            ("255, , 0, 0", true),
+            (",255, 0, 0", true),
+            ("255, 0, 0,,", true),
+        ];
+        for (s, expected) in test_data {
+            assert_eq!(has_empty_arg(s), expected, "{:?}", s);
+        }
+    }
+
     #[test]
     fn test_parse_percent_or_float() {
         let test_data = [
@@ -434,17 +822,45 @@ mod tests {
             ("50%", Some((0.5, true))),
             ("0", Some((0.0, false))),
             ("1", Some((1.0, false))),
+            ("none", Some((0.0, false))),
             ("0.5", Some((0.5, false))),
             ("100.0", Some((100.0, false))),
             ("-23.7", Some((-23.7, false))),
+            ("+50.5%", Some((0.505, true))),
+            ("-.5%", Some((-0.005, true))),
+            (".5", Some((0.5, false))),
+            ("+.5", Some((0.5, false))),
+            ("-0%", Some((0.0, true))),
             ("%", None),
             ("1x", None),
+            ("nan", None),
+            ("inf%", None),
+            ("-infinity", None),
+            ("1e39", None),
         ];
         for (s, expected) in test_data {
             assert_eq!(parse_percent_or_float(s), expected);
         }
     }
 
+    #[test]
+    fn test_parse_alpha() {
+        let test_data = [
+            ("none", Some(0.0)),
+            ("0.5", Some(0.5)),
+            ("50%", Some(0.5)),
+            ("1", Some(1.0)),
+            ("calc(1 / 2)", Some(0.5)),
+            ("calc(50% + 0.25)", Some(0.75)),
+            ("calc(alpha)", None),
+            ("nonee", None),
+            ("x", None),
+        ];
+        for (s, expected) in test_data {
+            assert_eq!(parse_alpha(s), expected);
+        }
+    }
+
     #[test]
     fn test_parse_percent_or_255() {
         let test_data = [
@@ -455,6 +871,12 @@ mod tests {
             ("0", Some((0.0, false))),
             ("255", Some((1.0, false))),
             ("127.5", Some((0.5, false))),
+            ("+50%", Some((0.5, true))),
+            ("+50.5%", Some((0.505, true))),
+            ("-.5%", Some((-0.005, true))),
+            (".5%", Some((0.005, true))),
+            ("+255", Some((1.0, false))),
+            ("-0", Some((0.0, false))),
             ("%", None),
             ("255x", None),
         ];
@@ -477,6 +899,8 @@ mod tests {
             ("-0.25turn", Some(-90.0)),
             ("O", None),
             ("Odeg", None),
+            ("NaNdeg", None),
+            ("infturn", None),
             ("rad", None),
         ];
         for (s, expected) in test_data {
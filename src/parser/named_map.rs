@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+use std::{error, fmt};
+
+use super::{parse, ParseColorError};
+use crate::Color;
+
+/// The error type of [`parse_named_map()`], identifying the invalid entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseNamedMapError {
+    /// The invalid entry, trimmed
+    pub entry: String,
+    /// The color parsing error, or `InvalidUnknown` if the entry is not `name: color`
+    pub kind: ParseColorError,
+}
+
+impl fmt::Display for ParseNamedMapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid entry {:?}: {}", self.entry, self.kind)
+    }
+}
+
+impl error::Error for ParseNamedMapError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&self.kind)
+    }
+}
+
+/// Parse a list of named colors, such as a theme, e.g. `"primary: #f00; accent: #00f"`.
+///
+/// Entries are separated by `;` and have the form `name: color`. Names and colors are trimmed,
+/// empty entries are skipped, and if a name is repeated the last color wins. The color can be in
+/// any format supported by [`parse()`].
+///
+/// # Examples
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let theme = csscolorparser::parse_named_map("primary: #f00; accent: rgb(0 0 255);")?;
+///
+/// assert_eq!(theme.len(), 2);
+/// assert_eq!(theme["primary"].to_hex_string(), "#ff0000");
+/// assert_eq!(theme["accent"].to_hex_string(), "#0000ff");
+/// # Ok(())
+/// # }
+/// ```
+pub fn parse_named_map(s: &str) -> Result<HashMap<String, Color>, ParseNamedMapError> {
+    let mut map = HashMap::new();
+
+    for entry in s.split(';').map(str::trim).filter(|e| !e.is_empty()) {
+        let err = |kind| ParseNamedMapError {
+            entry: entry.to_string(),
+            kind,
+        };
+        let (name, color) = match entry.split_once(':') {
+            Some((name, color)) if !name.trim().is_empty() => (name.trim(), color),
+            _ => return Err(err(ParseColorError::InvalidUnknown)),
+        };
+        let color = parse(color).map_err(err)?;
+        map.insert(name.to_string(), color);
+    }
+
+    Ok(map)
+}
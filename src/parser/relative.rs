@@ -0,0 +1,262 @@
+// CSS relative color syntax, e.g. `rgb(from red r g calc(b + 20))`.
+// https://www.w3.org/TR/css-color-5/#relative-colors
+
+use super::calc::{parse_calc, parse_calc_angle};
+use super::{function_error, parse, parse_angle, parse_number};
+use crate::{lab, Color, Illuminant, ParseColorError};
+
+// How a channel argument is interpreted.
+//
+// Percentages, both literal and inside `calc()`, resolve against the channel's reference range:
+// 255 for `r g b` (1 in `color()`); 100 for `s l`, `w b` and `s v`; 1 for `alpha`; 100, 125,
+// 125 for Lab `l a b` and 100, 150 for LCh `l c`; 1, 0.4, 0.4 for Oklab `l a b` and 1, 0.4 for
+// Oklch `l c`. Percentages are invalid for the hue.
+#[derive(Clone, Copy)]
+enum Channel {
+    // Hue angle in degrees
+    Hue,
+    // Number, with percentages relative to the given reference range
+    Number(f32),
+}
+
+use Channel::{Hue, Number};
+
+// The color space of a relative color, from the function name, or the color space argument of
+// `color()`.
+#[derive(Clone, Copy)]
+enum Space {
+    Srgb,
+    SrgbLinear,
+    DisplayP3,
+    Xyz(Illuminant),
+    Rgb,
+    Hsl,
+    Hwb,
+    Hsv,
+    #[cfg(feature = "lab")]
+    Lab,
+    #[cfg(feature = "lab")]
+    Lch,
+    Oklab,
+    Oklch,
+}
+
+// Parse the arguments of a relative color function `fname`, after the `from` keyword: the origin
+// color, three channels and the optional alpha. For `color()` the color space comes after the
+// origin color.
+pub(super) fn parse_relative(fname: &str, args: &[&str]) -> Result<Color, ParseColorError> {
+    let err = function_error(fname);
+
+    let mut args = args.to_vec();
+    let space = match fname {
+        "color" => {
+            if args.len() < 2 {
+                return Err(err);
+            }
+            match args.remove(1) {
+                "srgb" => Space::Srgb,
+                "srgb-linear" => Space::SrgbLinear,
+                "display-p3" => Space::DisplayP3,
+                "xyz" | "xyz-d65" => Space::Xyz(Illuminant::D65),
+                "xyz-d50" => Space::Xyz(Illuminant::D50),
+                _ => return Err(err),
+            }
+        }
+        "rgb" | "rgba" => Space::Rgb,
+        "hsl" | "hsla" => Space::Hsl,
+        "hwb" | "hwba" => Space::Hwb,
+        "hsv" | "hsva" => Space::Hsv,
+        #[cfg(feature = "lab")]
+        "lab" => Space::Lab,
+        #[cfg(feature = "lab")]
+        "lch" => Space::Lch,
+        "oklab" => Space::Oklab,
+        "oklch" => Space::Oklch,
+        _ => return Err(err),
+    };
+
+    if args.len() != 4 && args.len() != 5 {
+        return Err(err);
+    }
+
+    let origin = parse(args[0]).map_err(|_| err)?;
+
+    let (names, values, channels) = match space {
+        Space::Srgb => (
+            ["r", "g", "b"],
+            [origin.r, origin.g, origin.b],
+            [Number(1.0), Number(1.0), Number(1.0)],
+        ),
+        Space::SrgbLinear => {
+            let [r, g, b, _] = origin.to_linear_rgba();
+            (
+                ["r", "g", "b"],
+                [r, g, b],
+                [Number(1.0), Number(1.0), Number(1.0)],
+            )
+        }
+        Space::DisplayP3 => {
+            let [r, g, b, _] = origin.to_linear_rgba();
+            // Same transfer function as sRGB
+            let [r, g, b] = lab::linear_rgb_to_linear_p3([r, g, b]);
+            let p3 = Color::from_linear_rgba(r, g, b, 1.0);
+            (
+                ["r", "g", "b"],
+                [p3.r, p3.g, p3.b],
+                [Number(1.0), Number(1.0), Number(1.0)],
+            )
+        }
+        Space::Xyz(illuminant) => (
+            ["x", "y", "z"],
+            origin.to_xyz(illuminant),
+            [Number(1.0), Number(1.0), Number(1.0)],
+        ),
+        Space::Rgb => (
+            ["r", "g", "b"],
+            [origin.r * 255.0, origin.g * 255.0, origin.b * 255.0],
+            [Number(255.0), Number(255.0), Number(255.0)],
+        ),
+        Space::Hsl => {
+            let [h, s, l, _] = origin.to_hsla();
+            (
+                ["h", "s", "l"],
+                [h, s * 100.0, l * 100.0],
+                [Hue, Number(100.0), Number(100.0)],
+            )
+        }
+        Space::Hwb => {
+            let [h, w, b, _] = origin.to_hwba();
+            (
+                ["h", "w", "b"],
+                [h, w * 100.0, b * 100.0],
+                [Hue, Number(100.0), Number(100.0)],
+            )
+        }
+        Space::Hsv => {
+            let [h, s, v, _] = origin.to_hsva();
+            (
+                ["h", "s", "v"],
+                [h, s * 100.0, v * 100.0],
+                [Hue, Number(100.0), Number(100.0)],
+            )
+        }
+        #[cfg(feature = "lab")]
+        Space::Lab => {
+            let [l, a, b, _] = origin.to_laba();
+            (
+                ["l", "a", "b"],
+                [l, a, b],
+                [Number(100.0), Number(125.0), Number(125.0)],
+            )
+        }
+        #[cfg(feature = "lab")]
+        Space::Lch => {
+            let [l, c, h, _] = origin.to_lcha();
+            (
+                ["l", "c", "h"],
+                [l, c, h.to_degrees()],
+                [Number(100.0), Number(150.0), Hue],
+            )
+        }
+        Space::Oklab => {
+            let [l, a, b, _] = origin.to_oklaba();
+            (
+                ["l", "a", "b"],
+                [l, a, b],
+                [Number(1.0), Number(0.4), Number(0.4)],
+            )
+        }
+        Space::Oklch => {
+            let [l, c, h, _] = origin.to_oklcha();
+            (
+                ["l", "c", "h"],
+                [l, c, h.to_degrees()],
+                [Number(1.0), Number(0.4), Hue],
+            )
+        }
+    };
+
+    let vars = [
+        (names[0], values[0]),
+        (names[1], values[1]),
+        (names[2], values[2]),
+        ("alpha", origin.a),
+    ];
+
+    let mut v = [0.0; 3];
+    for i in 0..3 {
+        v[i] = parse_channel(args[i + 1], &vars, channels[i]).ok_or(err)?;
+    }
+
+    let alpha = match args.get(4) {
+        Some(s) => parse_channel(s, &vars, Number(1.0)).ok_or(err)?,
+        None => origin.a,
+    };
+    let alpha = alpha.clamp(0.0, 1.0);
+
+    let c = match space {
+        // Clamped like absolute `color()`
+        Space::Srgb => Color::new(v[0], v[1], v[2], alpha).clamp(),
+        Space::SrgbLinear => Color::from_linear_rgba(v[0], v[1], v[2], alpha).clamp(),
+        Space::DisplayP3 => {
+            let [r, g, b, _] = Color::new(v[0], v[1], v[2], alpha).to_linear_rgba();
+            let [r, g, b] = lab::linear_p3_to_linear_rgb([r, g, b]);
+            Color::from_linear_rgba(r, g, b, alpha).clamp()
+        }
+        Space::Xyz(illuminant) => Color {
+            a: alpha,
+            ..Color::from_xyz(v, illuminant)
+        }
+        .clamp(),
+        Space::Rgb => Color::new(
+            (v[0] / 255.0).clamp(0.0, 1.0),
+            (v[1] / 255.0).clamp(0.0, 1.0),
+            (v[2] / 255.0).clamp(0.0, 1.0),
+            alpha,
+        ),
+        Space::Hsl => Color::from_hsla(v[0], v[1] / 100.0, v[2] / 100.0, alpha),
+        Space::Hwb => Color::from_hwba(v[0], v[1] / 100.0, v[2] / 100.0, alpha),
+        Space::Hsv => Color::from_hsva(v[0], v[1] / 100.0, v[2] / 100.0, alpha),
+        #[cfg(feature = "lab")]
+        Space::Lab => Color::from_laba(v[0].clamp(0.0, 100.0), v[1], v[2], alpha),
+        #[cfg(feature = "lab")]
+        Space::Lch => Color::from_lcha(
+            v[0].clamp(0.0, 100.0),
+            v[1].max(0.0),
+            v[2].to_radians(),
+            alpha,
+        ),
+        Space::Oklab => Color::from_oklaba(v[0].clamp(0.0, 1.0), v[1], v[2], alpha),
+        Space::Oklch => Color::from_oklcha(
+            v[0].clamp(0.0, 1.0),
+            v[1].max(0.0),
+            v[2].to_radians(),
+            alpha,
+        ),
+    };
+
+    Ok(c)
+}
+
+// A channel keyword, `none`, `calc()` expression using the channel keywords, or a literal value.
+fn parse_channel(s: &str, vars: &[(&str, f32)], channel: Channel) -> Option<f32> {
+    if s == "none" {
+        return Some(0.0);
+    }
+    if let Some((_, v)) = vars.iter().find(|(k, _)| *k == s) {
+        return Some(*v);
+    }
+    if s.starts_with("calc(") {
+        return match channel {
+            Hue => parse_calc_angle(s, vars),
+            Number(range) => parse_calc(s, vars, Some(range)),
+        };
+    }
+    match channel {
+        Hue => parse_angle(s),
+        Number(range) => match s.strip_suffix('%') {
+            Some(s) => parse_number(s).map(|t| t / 100.0 * range),
+            None => parse_number(s),
+        },
+    }
+}
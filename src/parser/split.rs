@@ -0,0 +1,264 @@
+// Tokenizers for CSS component values, aware of nested parentheses.
+
+/// Iterator over the whitespace separated parts of a CSS value. Whitespace inside parentheses
+/// does not split, so `rgb(0 0 0) 50%` yields `rgb(0 0 0)` and `50%`. Only ASCII whitespace
+/// separates, as in CSS.
+pub(crate) struct SplitBySpace<'a> {
+    s: &'a str,
+}
+
+impl<'a> Iterator for SplitBySpace<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let s = self.s.trim_start_matches(|c: char| c.is_ascii_whitespace());
+        if s.is_empty() {
+            self.s = s;
+            return None;
+        }
+        let end = find_top_level(s, |c| c.is_ascii_whitespace()).unwrap_or(s.len());
+        self.s = &s[end..];
+        Some(&s[..end])
+    }
+}
+
+pub(crate) fn split_by_space(s: &str) -> SplitBySpace<'_> {
+    SplitBySpace { s }
+}
+
+/// Split a CSS value into its whitespace separated parts. Whitespace inside parentheses does not
+/// split, so function values like `rgb(0 0 0)` or `calc(1px + 2px)` stay whole.
+///
+/// Commas are not separators: in `a b, c` the parts are `a`, `b,` and `c`. Only ASCII
+/// whitespace separates, as in CSS, so e.g. a non-breaking space is part of a value.
+///
+/// # Examples
+/// ```
+/// use csscolorparser::split_css_value;
+///
+/// let parts: Vec<_> = split_css_value("2px 2px calc(4px + 1px) rgb(0 0 0 / 50%)").collect();
+///
+/// assert_eq!(parts, ["2px", "2px", "calc(4px + 1px)", "rgb(0 0 0 / 50%)"]);
+/// ```
+pub fn split_css_value(s: &str) -> impl Iterator<Item = &str> {
+    split_by_space(s)
+}
+
+/// Iterator over the arguments of a color function, separated by whitespace, `,` or `/`.
+/// Separators inside parentheses do not split, so `calc(1 / 2) 0 0` yields 3 arguments.
+pub(crate) struct SplitArgs<'a> {
+    s: &'a str,
+}
+
+impl<'a> Iterator for SplitArgs<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let s = self.s.trim_start_matches(is_arg_separator);
+        if s.is_empty() {
+            self.s = s;
+            return None;
+        }
+        let end = find_top_level(s, is_arg_separator).unwrap_or(s.len());
+        self.s = &s[end..];
+        Some(&s[..end])
+    }
+}
+
+pub(crate) fn split_args(s: &str) -> SplitArgs<'_> {
+    SplitArgs { s }
+}
+
+/// The separators between the arguments of a color function, with whitespace trimmed, so
+/// `255, 0 0 / 50%` yields `","`, `""` and `"/"`.
+pub(crate) fn arg_separators(s: &str) -> Vec<&str> {
+    let mut seps = Vec::new();
+    let mut prev_end = None;
+    let mut it = split_args(s);
+    while let Some(arg) = it.next() {
+        let end = s.len() - it.s.len();
+        let start = end - arg.len();
+        if let Some(prev_end) = prev_end {
+            seps.push(s[prev_end..start].trim());
+        }
+        prev_end = Some(end);
+    }
+    seps
+}
+
+// Only ASCII whitespace separates arguments, as in CSS, so e.g. a non-breaking space does not.
+fn is_arg_separator(c: char) -> bool {
+    c.is_ascii_whitespace() || c == ',' || c == '/'
+}
+
+/// Iterator over the comma separated parts of a CSS value, trimmed. Commas inside parentheses
+/// do not split. Empty parts are yielded as empty strings.
+#[derive(Default)]
+pub(crate) struct SplitByComma<'a> {
+    s: Option<&'a str>,
+}
+
+impl<'a> Iterator for SplitByComma<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let s = self.s?;
+        match find_top_level(s, |c| c == ',') {
+            Some(i) => {
+                self.s = Some(&s[i + 1..]);
+                Some(s[..i].trim())
+            }
+            None => {
+                self.s = None;
+                Some(s.trim())
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.s {
+            // At least one part, at most one more than the number of commas.
+            Some(s) => (1, Some(s.matches(',').count() + 1)),
+            None => (0, Some(0)),
+        }
+    }
+}
+
+impl DoubleEndedIterator for SplitByComma<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let s = self.s?;
+        match rfind_top_level(s, |c| c == ',') {
+            Some(i) => {
+                self.s = Some(&s[..i]);
+                Some(s[i + 1..].trim())
+            }
+            None => {
+                self.s = None;
+                Some(s.trim())
+            }
+        }
+    }
+}
+
+pub(crate) fn split_by_comma(s: &str) -> SplitByComma<'_> {
+    SplitByComma { s: Some(s) }
+}
+
+// Byte index of the first character matching `f` that is not nested inside parentheses.
+fn find_top_level<F: Fn(char) -> bool>(s: &str, f: F) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            c if depth == 0 && f(c) => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+// Byte index of the last character matching `f` that is not nested inside parentheses.
+fn rfind_top_level<F: Fn(char) -> bool>(s: &str, f: F) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in s.char_indices().rev() {
+        match c {
+            ')' => depth += 1,
+            '(' => depth = depth.saturating_sub(1),
+            c if depth == 0 && f(c) => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_by_space() {
+        let test_data: &[(&str, &[&str])] = &[
+            ("", &[]),
+            ("   ", &[]),
+            ("red", &["red"]),
+            ("  red   50% ", &["red", "50%"]),
+            ("rgb(0 0 0) 50%", &["rgb(0 0 0)", "50%"]),
+            ("to right", &["to", "right"]),
+            (
+                "hsl(calc(1 + 2) 50% 50%)\tblue",
+                &["hsl(calc(1 + 2) 50% 50%)", "blue"],
+            ),
+            ("a\u{a0}b c", &["a\u{a0}b", "c"]),
+        ];
+        for (s, expected) in test_data {
+            assert_eq!(&split_by_space(s).collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn test_split_args() {
+        let test_data: &[(&str, &[&str])] = &[
+            ("", &[]),
+            (" , / ", &[]),
+            ("255 0 0", &["255", "0", "0"]),
+            ("255,0, 0 ,0.5", &["255", "0", "0", "0.5"]),
+            ("255 0 0 / 50%", &["255", "0", "0", "50%"]),
+            ("255 0 0/50%", &["255", "0", "0", "50%"]),
+            (
+                "calc(255 / 2) calc(1 + 2 * 3), 0",
+                &["calc(255 / 2)", "calc(1 + 2 * 3)", "0"],
+            ),
+        ];
+        for (s, expected) in test_data {
+            assert_eq!(&split_args(s).collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn test_arg_separators() {
+        let test_data: &[(&str, &[&str])] = &[
+            ("", &[]),
+            ("255", &[]),
+            ("255 0 0", &["", ""]),
+            ("255,0, 0 ,0.5", &[",", ",", ","]),
+            ("255 0 0 / 50%", &["", "", "/"]),
+            ("255,0,0/50%", &[",", ",", "/"]),
+            ("255 , , 0", &[", ,"]),
+            ("calc(1 / 2) calc(3, 4),0", &["", ","]),
+        ];
+        for (s, expected) in test_data {
+            assert_eq!(&arg_separators(s), expected, "{}", s);
+        }
+    }
+
+    #[test]
+    fn test_split_by_comma() {
+        let test_data: &[(&str, &[&str])] = &[
+            ("", &[""]),
+            ("red", &["red"]),
+            ("red, blue", &["red", "blue"]),
+            ("rgb(0,0,0) 10%, #fff", &["rgb(0,0,0) 10%", "#fff"]),
+            ("a,,b,", &["a", "", "b", ""]),
+        ];
+        for (s, expected) in test_data {
+            assert_eq!(&split_by_comma(s).collect::<Vec<_>>(), expected);
+
+            let mut rev = split_by_comma(s).rev().collect::<Vec<_>>();
+            rev.reverse();
+            assert_eq!(&rev, expected);
+
+            let (lower, upper) = split_by_comma(s).size_hint();
+            assert!(lower <= expected.len() && expected.len() <= upper.unwrap());
+        }
+
+        // Mixed forward and backward iteration
+        let mut it = split_by_comma("a, rgb(1,2,3), b, c");
+        assert_eq!(it.next(), Some("a"));
+        assert_eq!(it.next_back(), Some("c"));
+        assert_eq!(it.next_back(), Some("b"));
+        assert_eq!(it.next(), Some("rgb(1,2,3)"));
+        assert_eq!(it.next(), None);
+        assert_eq!(it.next_back(), None);
+    }
+}
@@ -0,0 +1,56 @@
+use crate::{Color, ParseColorError};
+
+/// Parse an X11 color specification, as used in terminal emulator configs and escape sequences.
+///
+/// * `rgb:<red>/<green>/<blue>`: each component is 1 to 4 hexadecimal digits, scaled to its own
+///   range, so `rgb:f/8/0`, `rgb:ff/80/00` and `rgb:ffff/8080/0000` are all (nearly) the same.
+/// * `rgbi:<red>/<green>/<blue>`: each component is a number in the range [0..1].
+///
+/// # Examples
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use csscolorparser::parse_x11;
+///
+/// assert_eq!(parse_x11("rgb:ff/80/00")?.to_rgba8(), [255, 128, 0, 255]);
+/// assert_eq!(parse_x11("rgbi:1/0.5/0")?.to_rgba8(), [255, 128, 0, 255]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn parse_x11(s: &str) -> Result<Color, ParseColorError> {
+    let s = s.trim().to_lowercase();
+
+    if let Some(s) = s.strip_prefix("rgb:") {
+        let rgb = parse_components(s, |t| {
+            // from_str_radix also accepts a leading `+`
+            if t.is_empty() || t.len() > 4 || !t.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return None;
+            }
+            let max = (1u32 << (4 * t.len())) - 1;
+            u32::from_str_radix(t, 16)
+                .ok()
+                .map(|n| n as f32 / max as f32)
+        });
+        return rgb.ok_or(ParseColorError::InvalidHex);
+    }
+
+    if let Some(s) = s.strip_prefix("rgbi:") {
+        let rgb = parse_components(s, |t| {
+            t.parse::<f32>().ok().filter(|t| (0.0..=1.0).contains(t))
+        });
+        return rgb.ok_or(ParseColorError::InvalidRgb);
+    }
+
+    Err(ParseColorError::InvalidUnknown)
+}
+
+fn parse_components<F: Fn(&str) -> Option<f32>>(s: &str, f: F) -> Option<Color> {
+    let mut it = s.split('/');
+    let r = f(it.next()?)?;
+    let g = f(it.next()?)?;
+    let b = f(it.next()?)?;
+    if it.next().is_some() {
+        return None;
+    }
+    Some(Color::new(r, g, b, 1.0))
+}
@@ -0,0 +1,99 @@
+//! Serialize a [`Color`] as a struct of its float components, e.g.
+//! `{ "r": 1.0, "g": 0.0, "b": 0.0, "a": 1.0 }`, instead of a hex string.
+//!
+//! Unlike the hex string, this keeps the full precision of the components. When deserializing,
+//! a missing `a` defaults to 1.0.
+//!
+//! ```
+//! use csscolorparser::Color;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Theme {
+//!     #[serde(with = "csscolorparser::serde_struct")]
+//!     background: Color,
+//! }
+//! ```
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::Color;
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Color")]
+struct Rgba {
+    r: f32,
+    g: f32,
+    b: f32,
+    #[serde(default = "opaque")]
+    a: f32,
+}
+
+fn opaque() -> f32 {
+    1.0
+}
+
+/// Serialize the color as a struct with the fields `r`, `g`, `b` and `a`.
+pub fn serialize<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+    let Color { r, g, b, a } = *color;
+    Rgba { r, g, b, a }.serialize(serializer)
+}
+
+/// Deserialize the color from a struct with the fields `r`, `g`, `b` and the optional `a`.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+    let Rgba { r, g, b, a } = Rgba::deserialize(deserializer)?;
+    Ok(Color { r, g, b, a })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_test::{assert_de_tokens, assert_tokens, Token};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Wrapper(#[serde(with = "super")] Color);
+
+    #[test]
+    fn test_serde_struct() {
+        assert_tokens(
+            &Wrapper(Color::new(1.0, 0.5, 0.0, 0.25)),
+            &[
+                Token::NewtypeStruct { name: "Wrapper" },
+                Token::Struct {
+                    name: "Color",
+                    len: 4,
+                },
+                Token::Str("r"),
+                Token::F32(1.0),
+                Token::Str("g"),
+                Token::F32(0.5),
+                Token::Str("b"),
+                Token::F32(0.0),
+                Token::Str("a"),
+                Token::F32(0.25),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_serde_struct_default_alpha() {
+        assert_de_tokens(
+            &Wrapper(Color::new(0.0, 0.0, 1.0, 1.0)),
+            &[
+                Token::NewtypeStruct { name: "Wrapper" },
+                Token::Struct {
+                    name: "Color",
+                    len: 3,
+                },
+                Token::Str("r"),
+                Token::F32(0.0),
+                Token::Str("g"),
+                Token::F32(0.0),
+                Token::Str("b"),
+                Token::F32(1.0),
+                Token::StructEnd,
+            ],
+        );
+    }
+}
@@ -1,3 +1,6 @@
+// Reference values are written with their full f64 precision.
+#![allow(clippy::excessive_precision)]
+
 use csscolorparser::Color;
 use std::convert::TryFrom;
 
@@ -43,20 +46,17 @@ fn basic() {
     assert_eq!(c.to_hsla(), [0.0, 0.0, 0.5, 1.0]);
     assert_eq!(c.to_hwba(), [0.0, 0.5, 0.5, 1.0]);
 
-    #[cfg(feature = "lab")]
-    {
-        let c = Color::from_laba(0.0, 0.0, 0.0, 1.0);
-        assert_eq!(c.to_rgba8(), [0, 0, 0, 255]);
+    let c = Color::from_laba(0.0, 0.0, 0.0, 1.0);
+    assert_eq!(c.to_rgba8(), [0, 0, 0, 255]);
 
-        let c = Color::from_laba(100.0, 0.0, 0.0, 1.0);
-        assert_eq!(c.to_rgba8(), [255, 255, 255, 255]);
+    let c = Color::from_laba(100.0, 0.0, 0.0, 1.0);
+    assert_eq!(c.to_rgba8(), [255, 255, 255, 255]);
 
-        let c = Color::from_lcha(0.0, 0.0, 0.0, 1.0);
-        assert_eq!(c.to_rgba8(), [0, 0, 0, 255]);
+    let c = Color::from_lcha(0.0, 0.0, 0.0, 1.0);
+    assert_eq!(c.to_rgba8(), [0, 0, 0, 255]);
 
-        let c = Color::from_lcha(100.0, 0.0, 0.0, 1.0);
-        assert_eq!(c.to_rgba8(), [255, 255, 255, 255]);
-    }
+    let c = Color::from_lcha(100.0, 0.0, 0.0, 1.0);
+    assert_eq!(c.to_rgba8(), [255, 255, 255, 255]);
 
     assert_eq!(Color::default().to_rgba8(), [0, 0, 0, 255]);
 
@@ -104,6 +104,50 @@ fn basic() {
     assert_eq!(c.to_rgba16(), [65535, 32768, 0, 65535]);
 }
 
+#[test]
+fn format_hex() {
+    let c = Color::from_rgba8(255, 170, 0, 255);
+    assert_eq!(format!("{:x}", c), "ffaa00");
+    assert_eq!(format!("{:X}", c), "FFAA00");
+    assert_eq!(format!("{:#x}", c), "#ffaa00");
+    assert_eq!(format!("{:#X}", c), "#FFAA00");
+
+    let c = Color::from_rgba8(10, 11, 12, 128);
+    assert_eq!(format!("{:x}", c), "0a0b0c80");
+    assert_eq!(format!("{:X}", c), "0A0B0C80");
+    assert_eq!(format!("{:#x}", c), c.to_hex_string());
+    assert_eq!(format!("{:#X}", c), "#0A0B0C80");
+
+    // Width, fill and alignment
+    let c = Color::from_rgba8(255, 170, 0, 255);
+    assert_eq!(format!("{:>10x}", c), "    ffaa00");
+    assert_eq!(format!("{:<10X}|", c), "FFAA00    |");
+    assert_eq!(format!("{:*^#11x}", c), "**#ffaa00**");
+}
+
+#[test]
+fn out_of_range_to_integer() {
+    for (t, expected8, expected16) in [
+        (-1.0, 0, 0),
+        (2.0, 255, 65535),
+        (-1000.0, 0, 0),
+        (1000.0, 255, 65535),
+        (f32::INFINITY, 255, 65535),
+        (f32::NEG_INFINITY, 0, 0),
+    ] {
+        let c = Color::new(t, t, t, t);
+        assert_eq!(c.to_rgba8(), [expected8; 4], "{}", t);
+        assert_eq!(c.to_rgba16(), [expected16; 4], "{}", t);
+        assert_eq!(c.to_u32_argb(), u32::from_be_bytes([expected8; 4]), "{}", t);
+    }
+
+    let c = Color::new(-1.0, 2.0, 0.5, 1.0);
+    assert_eq!(c.to_rgba8(), [0, 255, 128, 255]);
+    assert_eq!(c.to_rgba16(), [0, 65535, 32768, 65535]);
+    assert_eq!(c.to_linear_rgba_u8(), [0, 255, 55, 255]);
+    assert_eq!(c.to_hex_string(), "#00ff80");
+}
+
 #[test]
 fn convert_colors() {
     let colors = &[
@@ -175,19 +219,82 @@ fn convert_colors() {
         let x = Color::from_oklaba(a, b, c, d);
         assert_eq!(s, &x.to_hex_string());
 
-        #[cfg(feature = "lab")]
-        {
-            let [a, b, c, d] = col.to_laba();
-            let x = Color::from_laba(a, b, c, d);
-            assert_eq!(s, &x.to_hex_string());
+        let [a, b, c, d] = col.to_laba();
+        let x = Color::from_laba(a, b, c, d);
+        assert_eq!(s, &x.to_hex_string());
 
-            let [a, b, c, d] = col.to_lcha();
-            let x = Color::from_lcha(a, b, c, d);
-            assert_eq!(s, &x.to_hex_string());
+        let [a, b, c, d] = col.to_lcha();
+        let x = Color::from_lcha(a, b, c, d);
+        assert_eq!(s, &x.to_hex_string());
+    }
+}
+
+#[test]
+fn xyz() {
+    use csscolorparser::Illuminant;
+
+    let white = Color::new(1.0, 1.0, 1.0, 1.0);
+    let data = [
+        (
+            Illuminant::D65,
+            [0.3127 / 0.3290, 1.0, (1.0 - 0.3127 - 0.3290) / 0.3290],
+        ),
+        (
+            Illuminant::D50,
+            [0.3457 / 0.3585, 1.0, (1.0 - 0.3457 - 0.3585) / 0.3585],
+        ),
+    ];
+    for (illuminant, expected) in data {
+        let xyz = white.to_xyz(illuminant);
+        for i in 0..3 {
+            assert!(
+                (xyz[i] - expected[i]).abs() < 1e-4,
+                "{:?} {:?}",
+                illuminant,
+                xyz
+            );
+        }
+        assert_eq!(
+            Color::from_xyz(expected, illuminant).to_rgba8(),
+            [255, 255, 255, 255]
+        );
+        assert_eq!(
+            Color::from_xyz([0.0; 3], illuminant).to_rgba8(),
+            [0, 0, 0, 255]
+        );
+    }
+
+    // sRGB red in XYZ D65
+    let xyz = Color::new(1.0, 0.0, 0.0, 0.5).to_xyz(Illuminant::D65);
+    for (a, b) in xyz.iter().zip([0.4124, 0.2126, 0.0193]) {
+        assert!((a - b).abs() < 1e-4, "{:?}", xyz);
+    }
+
+    for s in ["#bad455", "#000000", "#ff8000", "#3366cc", "#808080"] {
+        let c = csscolorparser::parse(s).unwrap();
+        for illuminant in [Illuminant::D65, Illuminant::D50] {
+            let x = Color::from_xyz(c.to_xyz(illuminant), illuminant);
+            assert_eq!(x.to_hex_string(), s);
         }
     }
 }
 
+#[test]
+fn hcl() {
+    let data = [
+        (0.0, 0.0, 0.0, 1.0),
+        (0.7, 50.0, 60.0, 1.0),
+        (4.2, 30.0, 35.0, 0.5),
+        (2.1, 80.0, 90.0, 0.25),
+    ];
+    for (h, c, l, alpha) in data {
+        let x = Color::from_hcl(h, c, l, alpha);
+        assert_eq!(x, Color::from_lcha(l, c, h, alpha));
+        let [l, c, h, alpha] = x.to_lcha();
+        assert_eq!(x.to_hcl(), [h, c, l, alpha]);
+    }
+}
+
 #[test]
 fn red() {
     let data = &[
@@ -238,20 +345,1688 @@ fn interpolate() {
         [0, 0, 255, 255]
     );
 
-    assert_eq!(a.interpolate_hsv(&b, 0.0).to_rgba8(), [0, 255, 0, 255]);
-    assert_eq!(a.interpolate_hsv(&b, 0.5).to_rgba8(), [0, 255, 255, 255]);
-    assert_eq!(a.interpolate_hsv(&b, 1.0).to_rgba8(), [0, 0, 255, 255]);
+    assert!(a.interpolate_hsv(&b, 0.0).eq_rgba8([0, 255, 0, 255]));
+    assert!(a.interpolate_hsv(&b, 0.5).eq_rgba8([0, 255, 255, 255]));
+    assert!(a.interpolate_hsv(&b, 1.0).eq_rgba8([0, 0, 255, 255]));
 
-    assert_eq!(a.interpolate_oklab(&b, 0.0).to_rgba8(), [0, 255, 0, 255]);
-    assert_eq!(a.interpolate_oklab(&b, 0.5).to_rgba8(), [0, 170, 191, 255]);
-    assert_eq!(a.interpolate_oklab(&b, 1.0).to_rgba8(), [0, 0, 255, 255]);
+    assert!(a.interpolate_oklab(&b, 0.0).eq_rgba8([0, 255, 0, 255]));
+    assert!(a.interpolate_oklab(&b, 0.5).eq_rgba8([0, 170, 191, 255]));
+    assert!(a.interpolate_oklab(&b, 1.0).eq_rgba8([0, 0, 255, 255]));
 
-    #[cfg(feature = "lab")]
-    {
-        assert_eq!(a.interpolate_lab(&b, 0.0).to_rgba8(), [0, 255, 0, 255]);
-        assert_eq!(a.interpolate_lab(&b, 1.0).to_rgba8(), [0, 0, 255, 255]);
+    assert_eq!(a.interpolate_lab(&b, 0.0).to_rgba8(), [0, 255, 0, 255]);
+    assert_eq!(a.interpolate_lab(&b, 1.0).to_rgba8(), [0, 0, 255, 255]);
+
+    assert_eq!(a.interpolate_lch(&b, 0.0).to_rgba8(), [0, 255, 0, 255]);
+    assert_eq!(a.interpolate_lch(&b, 1.0).to_rgba8(), [0, 0, 255, 255]);
+}
+
+#[test]
+fn hue_difference() {
+    let red = Color::new(1.0, 0.0, 0.0, 1.0);
+    let cyan = Color::new(0.0, 1.0, 1.0, 1.0);
+    let lime = Color::new(0.0, 1.0, 0.0, 1.0);
+    let blue = Color::new(0.0, 0.0, 1.0, 1.0);
+    let gray = Color::new(0.5, 0.5, 0.5, 1.0);
+
+    assert_eq!(red.hue_difference(&cyan), 180.0);
+    assert_eq!(cyan.hue_difference(&red), 180.0);
+    assert_eq!(red.hue_difference(&lime), 120.0);
+    assert_eq!(red.hue_difference(&blue), -120.0);
+    assert_eq!(red.hue_difference(&red), 0.0);
+    assert!(red.hue_difference(&gray).is_nan());
+    assert!(gray.hue_difference(&red).is_nan());
+
+    assert_eq!(red.hue_rotate_to(120.0).to_rgba8(), [0, 255, 0, 255]);
+    assert_eq!(red.hue_rotate_to(180.0).to_rgba8(), [0, 255, 255, 255]);
+    assert_eq!(
+        Color::new(1.0, 0.0, 0.0, 0.5)
+            .hue_rotate_to(240.0)
+            .to_rgba8(),
+        [0, 0, 255, 128]
+    );
+    assert_eq!(gray.hue_rotate_to(120.0), gray);
+}
+
+#[test]
+fn to_css_hwb() {
+    let c = Color::new(1.0, 0.0, 0.0, 1.0);
+    assert_eq!(c.to_css_hwb(), "hwb(0 0% 0%)");
+    assert_eq!(c.to_css_hwb_prec(2), "hwb(0 0% 0%)");
+
+    let c = Color::new(0.0, 1.0, 0.0, 0.5);
+    assert_eq!(c.to_css_hwb(), "hwb(120 0% 0% / 50%)");
+
+    let c = csscolorparser::parse("hwb(290 30.5% 10.2%)").unwrap();
+    assert_eq!(c.to_css_hwb(), "hwb(290 30% 10%)");
+    assert_eq!(c.to_css_hwb_prec(1), "hwb(290 30.5% 10.2%)");
+
+    for s in ["hwb(290 30.5% 10.2%)", "hwb(45.5 12.25% 40% / 50%)"] {
+        let c = csscolorparser::parse(s).unwrap();
+        let x = csscolorparser::parse(&c.to_css_hwb_prec(3)).unwrap();
+        assert_eq!(c.to_rgba16(), x.to_rgba16());
+    }
+}
+
+#[test]
+fn try_new() {
+    assert_eq!(
+        Color::try_new(1.0, 0.5, 0.0, 1.0),
+        Some(Color::new(1.0, 0.5, 0.0, 1.0))
+    );
+    assert_eq!(Color::try_new(f32::NAN, 0.0, 0.0, 1.0), None);
+    assert_eq!(Color::try_new(0.0, f32::INFINITY, 0.0, 1.0), None);
+    assert_eq!(Color::try_new(0.0, 0.0, f32::NEG_INFINITY, 1.0), None);
+    assert_eq!(Color::try_new(0.0, 0.0, 0.0, f32::NAN), None);
+
+    // new() accepts NaN, as a missing component, in debug and release builds
+    let c = Color::new(f32::NAN, 0.0, 0.0, 1.0);
+    assert!(c.r.is_nan());
+    assert_eq!(c.to_css_rgb(), "rgb(none 0 0)");
+}
+
+#[test]
+fn oklch() {
+    let data = [
+        "#000000",
+        "#ffffff",
+        "#ff0000",
+        "#6a8caf",
+        "#ffb31a",
+        "#825dfa6d",
+    ];
+    for s in data {
+        let col = csscolorparser::parse(s).unwrap();
+        let [l, c, h, alpha] = col.to_oklcha();
+        assert!(c >= 0.0);
+        assert!((0.0..std::f32::consts::TAU).contains(&h));
+        let x = Color::from_oklcha(l, c, h, alpha);
+        assert_eq!(s, &x.to_hex_string());
+    }
+
+    let c = Color::from_html("#6a8caf").unwrap();
+    let [l1, c1, h1, a1] = c.to_oklcha();
+
+    let x = c.saturate_oklch(0.05);
+    let [l2, c2, h2, a2] = x.to_oklcha();
+    assert!((c2 - (c1 + 0.05)).abs() < 1e-4);
+    assert!((l2 - l1).abs() < 1e-4);
+    assert!((h2 - h1).abs() < 1e-4);
+    assert_eq!(a1, a2);
+
+    let x = c.saturate_oklch(-1.0);
+    assert!(x.to_oklcha()[1] < 1e-4);
+    assert_eq!(x.to_rgba8()[0], x.to_rgba8()[1]);
+    assert_eq!(x.to_rgba8()[1], x.to_rgba8()[2]);
+
+    let x = c.set_chroma_oklch(0.1);
+    let [_, c2, h2, _] = x.to_oklcha();
+    assert!((c2 - 0.1).abs() < 1e-4);
+    assert!((h2 - h1).abs() < 1e-4);
+}
+
+#[test]
+fn tints_and_shades() {
+    let base = Color::new(0.2, 0.4, 0.8, 0.6);
+    let hue = base.hue_oklch_deg();
+
+    let scale = base.tints_and_shades(10);
+    assert_eq!(scale.len(), 10);
+    assert!((scale[0].lightness_oklch() - 0.05).abs() < 1e-3);
+    assert!((scale[9].lightness_oklch() - 0.95).abs() < 1e-3);
+    for (i, c) in scale.iter().enumerate() {
+        assert!([c.r, c.g, c.b].iter().all(|t| (0.0..=1.0).contains(t)));
+        assert_eq!(c.a, 0.6);
+        if i > 0 {
+            assert!(c.lightness_oklch() > scale[i - 1].lightness_oklch());
+        }
+        // The hue is meaningless without chroma
+        if c.chroma_oklch() > 0.01 {
+            assert!((c.hue_oklch_deg() - hue).abs() < 0.5, "{} {:?}", i, c);
+        }
+        assert!(c.chroma_oklch() <= base.chroma_oklch() + 1e-4);
+    }
+
+    assert!(base.tints_and_shades(0).is_empty());
+    let one = base.tints_and_shades(1);
+    assert_eq!(one.len(), 1);
+    assert!((one[0].lightness_oklch() - 0.5).abs() < 1e-3);
+}
+
+#[test]
+fn perceptual_sort_key() {
+    // Oklch hues of red, yellow, green, cyan, blue and magenta are increasing
+    let rainbow = [
+        Color::new(1.0, 0.0, 0.0, 1.0),
+        Color::new(1.0, 1.0, 0.0, 1.0),
+        Color::new(0.0, 1.0, 0.0, 1.0),
+        Color::new(0.0, 1.0, 1.0, 1.0),
+        Color::new(0.0, 0.0, 1.0, 1.0),
+        Color::new(1.0, 0.0, 1.0, 1.0),
+    ];
+    let grays = [
+        Color::new(0.0, 0.0, 0.0, 1.0),
+        Color::new(0.5, 0.5, 0.5, 1.0),
+        Color::new(1.0, 1.0, 1.0, 1.0),
+    ];
+
+    let mut colors = vec![
+        grays[1].clone(),
+        rainbow[4].clone(),
+        rainbow[0].clone(),
+        grays[2].clone(),
+        rainbow[3].clone(),
+        rainbow[5].clone(),
+        grays[0].clone(),
+        rainbow[2].clone(),
+        rainbow[1].clone(),
+    ];
+    colors.sort_by_key(|c| c.perceptual_sort_key());
+    assert_eq!(&colors[..6], &rainbow);
+    assert_eq!(&colors[6..], &grays);
+
+    for c in &grays {
+        assert_eq!(c.perceptual_sort_key().0, 360);
+    }
+    assert_eq!(grays[0].perceptual_sort_key(), (360, 0));
+    assert_eq!(grays[2].perceptual_sort_key(), (360, 1000));
+
+    // Same hue, sorted by lightness
+    let dark = Color::new(0.5, 0.0, 0.0, 1.0).perceptual_sort_key();
+    let light = Color::new(1.0, 0.0, 0.0, 1.0).perceptual_sort_key();
+    assert!(dark.1 < light.1);
+}
+
+#[test]
+fn saturate_desaturate() {
+    let in_gamut = |c: &Color| [c.r, c.g, c.b].iter().all(|t| (0.0..=1.0).contains(t));
+
+    let c = Color::new(0.6, 0.4, 0.3, 0.75);
+    let [l1, c1, h1, a1] = c.to_oklcha();
+
+    let x = c.saturate(0.02);
+    let [l2, c2, h2, a2] = x.to_oklcha();
+    assert!((c2 - (c1 + 0.02)).abs() < 1e-4);
+    assert!((l2 - l1).abs() < 1e-4);
+    assert!((h2 - h1).abs() < 1e-3);
+    assert_eq!(a1, a2);
+
+    let x = c.desaturate(0.02);
+    assert!((x.chroma_oklch() - (c1 - 0.02)).abs() < 1e-4);
+    assert_eq!(x, c.saturate(-0.02));
+
+    // Out of gamut chroma is mapped back into the sRGB gamut
+    let x = c.saturate(1.0);
+    assert!(in_gamut(&x), "{:?}", x);
+    assert!(x.chroma_oklch() > c1);
+    assert!(!in_gamut(&c.saturate_oklch(1.0)));
+
+    // Desaturating to the limit gives a gray
+    for c in [
+        Color::new(0.6, 0.4, 0.3, 1.0),
+        Color::new(1.0, 0.0, 0.0, 1.0),
+        Color::new(0.1, 0.2, 0.9, 0.5),
+    ] {
+        let x = c.desaturate(1.0);
+        assert!(in_gamut(&x));
+        assert!(x.chroma_oklch() < 1e-4);
+        let [r, g, b, _] = x.to_rgba8();
+        assert!(r.abs_diff(g) <= 1 && g.abs_diff(b) <= 1, "{:?}", x);
+        assert_eq!(x.a, c.a);
+    }
+}
+
+#[test]
+fn with_oklch() {
+    let c = Color::new(0.2, 0.6, 0.4, 0.7);
+    let [l1, c1, _, a1] = c.to_oklcha();
+
+    let x = c.with_oklch(None, None, Some(300.0));
+    let [l2, c2, _, a2] = x.to_oklcha();
+    assert!((l2 - l1).abs() < 1e-4);
+    assert!((c2 - c1).abs() < 1e-4);
+    assert!((x.hue_oklch_deg() - 300.0).abs() < 1e-2);
+    assert_eq!(a1, a2);
+
+    let x = c.with_oklch(None, None, None);
+    assert_eq!(x.to_rgba8(), c.to_rgba8());
+
+    let x = c.with_oklch(Some(1.5), Some(-0.1), Some(-60.0));
+    let [l2, c2, _, _] = x.to_oklcha();
+    assert!((l2 - 1.0).abs() < 1e-4);
+    assert!(c2 < 1e-4);
+
+    let x = c.with_oklch(Some(0.5), None, Some(420.0));
+    assert!((x.lightness_oklch() - 0.5).abs() < 1e-4);
+    assert!((x.hue_oklch_deg() - 60.0).abs() < 1e-2);
+}
+
+#[test]
+fn map_linear_rgb() {
+    let c = Color::new(1.0, 0.5, 0.0, 0.5);
+    let x = c.map_linear_rgb(|rgb| rgb);
+    assert_eq!(c.to_rgba8(), x.to_rgba8());
+
+    let x = c.map_linear_rgb(|[r, g, b]| [r * 0.5, g * 0.5, b * 0.5]);
+    let [r1, g1, b1, a1] = c.to_linear_rgba();
+    let [r2, g2, b2, a2] = x.to_linear_rgba();
+    assert!((r1 * 0.5 - r2).abs() < 1e-6);
+    assert!((g1 * 0.5 - g2).abs() < 1e-6);
+    assert!((b1 * 0.5 - b2).abs() < 1e-6);
+    assert_eq!(a1, a2);
+}
+
+#[test]
+fn to_css_oklab() {
+    let data = [
+        (Color::new(0.0, 0.0, 0.0, 1.0), "oklab(0 0 0)"),
+        (Color::new(1.0, 1.0, 1.0, 1.0), "oklab(1 0 0)"),
+        (Color::new(1.0, 0.0, 0.0, 1.0), "oklab(0.628 0.2249 0.1258)"),
+        (
+            Color::new(0.0, 1.0, 0.0, 0.5),
+            "oklab(0.8664 -0.2339 0.1795 / 50%)",
+        ),
+    ];
+    for (c, s) in data {
+        assert_eq!(c.to_css_oklab(), s);
+        assert_eq!(c.to_css_oklab_snapped(0.0), s);
+        let x = csscolorparser::parse(s).unwrap();
+        assert_eq!(c.to_rgba8(), x.to_rgba8());
+    }
+
+    let c = Color::from_rgba8(128, 128, 129, 255);
+    assert_eq!(c.to_css_oklab(), "oklab(0.6002 0.0004 -0.0014)");
+    assert_eq!(c.to_css_oklab_snapped(0.005), "oklab(0.6002 0 0)");
+
+    let c = Color::new(1.0, 0.0, 0.0, 1.0);
+    assert_eq!(c.to_css_oklab_snapped(0.005), "oklab(0.628 0.2249 0.1258)");
+}
+
+#[test]
+fn equals_css() {
+    let a = Color::new(1.0, 0.0, 0.0, 1.0);
+    assert!(a.equals_css(&a));
+    assert!(a.equals_css(&Color::new(0.999, 0.001, 0.0, 1.0)));
+    assert!(a.equals_css(&Color::from_html("#f00").unwrap()));
+    assert!(a.equals_css(&Color::from_hsla(0.0, 1.0, 0.5, 1.0)));
+    assert!(!a.equals_css(&Color::new(0.99, 0.0, 0.0, 1.0)));
+    assert!(!a.equals_css(&Color::new(1.0, 0.0, 0.0, 0.5)));
+}
 
-        assert_eq!(a.interpolate_lch(&b, 0.0).to_rgba8(), [0, 255, 0, 255]);
-        assert_eq!(a.interpolate_lch(&b, 1.0).to_rgba8(), [0, 0, 255, 255]);
+#[test]
+fn eq_rgba8() {
+    let c = Color::new(1.0, 0.5, 0.0, 1.0);
+    assert!(c.eq_rgba8([255, 128, 0, 255]));
+    assert!(Color::new(0.999, 0.501, 0.001, 1.0).eq_rgba8([255, 128, 0, 255]));
+    assert!(!c.eq_rgba8([255, 127, 0, 255]));
+    assert!(!c.eq_rgba8([255, 128, 0, 254]));
+}
+
+#[test]
+fn scale_rgb() {
+    let c = Color::new(1.0, 0.5, 0.2, 0.7);
+    assert_eq!(c.scale_rgb(0.5), Color::new(0.5, 0.25, 0.1, 0.7));
+    assert_eq!(c.scale_rgb(1.0), c);
+    assert_eq!(c.scale_rgb(0.0), Color::new(0.0, 0.0, 0.0, 0.7));
+    // not clamped
+    assert_eq!(c.scale_rgb(2.0), Color::new(2.0, 1.0, 0.4, 0.7));
+}
+
+#[test]
+fn to_css_lab() {
+    let data = [
+        (
+            Color::new(0.0, 0.0, 0.0, 1.0),
+            "lab(0 0 0)",
+            "lch(0 0 none)",
+        ),
+        (
+            Color::new(1.0, 1.0, 1.0, 1.0),
+            "lab(100 0 0)",
+            "lch(100 0 none)",
+        ),
+        (
+            Color::new(1.0, 0.0, 0.0, 1.0),
+            "lab(54.29 80.8 69.89)",
+            "lch(54.29 106.84 40.86)",
+        ),
+        (
+            Color::new(0.0, 0.0, 1.0, 0.5),
+            "lab(29.57 68.29 -112.03 / 50%)",
+            "lch(29.57 131.2 301.36 / 50%)",
+        ),
+    ];
+    for (c, lab, lch) in data {
+        assert_eq!(c.to_css_lab(), lab);
+        assert_eq!(c.to_css_lch(), lch);
+
+        #[cfg(feature = "lab")]
+        {
+            assert_eq!(csscolorparser::parse(lab).unwrap().to_rgba8(), c.to_rgba8());
+            assert_eq!(csscolorparser::parse(lch).unwrap().to_rgba8(), c.to_rgba8());
+        }
+    }
+}
+
+#[test]
+fn to_css_lab_percent() {
+    let data = [
+        (Color::new(0.0, 0.0, 0.0, 1.0), "lab(0% 0 0)"),
+        (Color::new(1.0, 1.0, 1.0, 1.0), "lab(100% 0 0)"),
+        (Color::new(1.0, 0.0, 0.0, 1.0), "lab(54.29% 80.8 69.89)"),
+        (
+            Color::new(0.0, 0.0, 1.0, 0.5),
+            "lab(29.57% 68.29 -112.03 / 50%)",
+        ),
+    ];
+    for (c, s) in data {
+        assert_eq!(c.to_css_lab_percent(), s);
+
+        #[cfg(feature = "lab")]
+        {
+            let c2 = csscolorparser::parse(s).unwrap();
+            assert_eq!(c2.to_rgba8(), c.to_rgba8());
+            assert_eq!(c2.to_css_lab_percent(), s);
+        }
+    }
+}
+
+#[test]
+fn to_css_oklch_percent() {
+    let data = [
+        (Color::new(0.0, 0.0, 0.0, 1.0), "oklch(0% 0% none)"),
+        (Color::new(1.0, 1.0, 1.0, 1.0), "oklch(100% 0% none)"),
+        (Color::new(1.0, 0.0, 0.0, 1.0), "oklch(62.8% 64.42% 29.23)"),
+        (
+            Color::new(0.0, 0.0, 1.0, 0.5),
+            "oklch(45.2% 78.3% 264.05 / 50%)",
+        ),
+    ];
+    for (c, s) in data {
+        assert_eq!(c.to_css_oklch_percent(), s);
+
+        let c2 = csscolorparser::parse(s).unwrap();
+        assert_eq!(c2.to_rgba8(), c.to_rgba8());
+        assert_eq!(c2.to_css_oklch_percent(), s);
+    }
+}
+
+#[test]
+fn luminance_y() {
+    use csscolorparser::Illuminant;
+
+    let data = [
+        (Color::new(1.0, 0.0, 0.0, 1.0), 0.2126),
+        (Color::new(0.0, 1.0, 0.0, 1.0), 0.7152),
+        (Color::new(0.0, 0.0, 1.0, 1.0), 0.0722),
+        (Color::new(1.0, 1.0, 1.0, 1.0), 1.0),
+        (Color::new(0.0, 0.0, 0.0, 0.5), 0.0),
+        (Color::new(0.5, 0.5, 0.5, 1.0), 0.21404114),
+    ];
+    for (c, y) in data {
+        assert!((c.luminance_y() - y).abs() < 1e-5, "{:?}", c);
+        // Same as the Y of CIE XYZ
+        assert!((c.luminance_y() - c.to_xyz(Illuminant::D65)[1]).abs() < 1e-4);
+    }
+
+    // Not clamped
+    let c = Color::new(1.2, 1.0, 1.0, 1.0);
+    assert!(c.luminance_y() > 1.0);
+}
+
+#[test]
+fn luminance_contrast_ratio() {
+    let black = Color::new(0.0, 0.0, 0.0, 1.0);
+    let white = Color::new(1.0, 1.0, 1.0, 1.0);
+    assert_eq!(black.luminance(), 0.0);
+    assert!((white.luminance() - 1.0).abs() < 1e-6);
+    assert!((Color::new(1.0, 0.0, 0.0, 1.0).luminance() - 0.2126).abs() < 1e-6);
+    assert!((black.contrast_ratio(&white) - 21.0).abs() < 1e-4);
+    assert!((white.contrast_ratio(&black) - 21.0).abs() < 1e-4);
+    assert_eq!(white.contrast_ratio(&white), 1.0);
+
+    // #767676 is the lightest gray passing WCAG AA (4.5) on white
+    let gray = Color::from_rgba8(0x76, 0x76, 0x76, 255);
+    assert!((gray.contrast_ratio(&white) - 4.54).abs() < 0.01);
+    let gray = Color::from_rgba8(0x77, 0x77, 0x77, 255);
+    assert!(gray.contrast_ratio(&white) < 4.5);
+
+    // Alpha is ignored
+    let c = Color::new(1.0, 0.0, 0.0, 0.0);
+    assert_eq!(c.luminance(), Color::new(1.0, 0.0, 0.0, 1.0).luminance());
+    assert!((c.contrast_ratio(&black) - 5.252).abs() < 1e-3);
+
+    // Clamped, unlike luminance_y()
+    let c = Color::new(1.5, 1.0, 1.0, 1.0);
+    assert_eq!(c.luminance(), white.luminance());
+    assert!(c.luminance_y() > 1.0);
+}
+
+#[test]
+fn oklch_accessors() {
+    let data = [
+        ("#ff0000", 0.62796, 0.25768, 29.23388),
+        ("#00ff00", 0.86644, 0.29483, 142.49535),
+        ("#0000ff", 0.45201, 0.31321, 264.05202),
+        ("#ffffff", 1.0, 0.0, f32::NAN),
+        ("#000000", 0.0, 0.0, f32::NAN),
+    ];
+    for (s, l, c, h) in data {
+        let col = csscolorparser::parse(s).unwrap();
+        assert!((col.lightness_oklch() - l).abs() < 1e-4, "{}", s);
+        assert!((col.chroma_oklch() - c).abs() < 1e-4, "{}", s);
+        if !h.is_nan() {
+            assert!((col.hue_oklch_deg() - h).abs() < 1e-2, "{}", s);
+        }
+    }
+}
+
+#[test]
+fn quantize() {
+    let red = Color::new(1.0, 0.0, 0.0, 1.0);
+    let lime = Color::new(0.0, 1.0, 0.0, 1.0);
+    let blue = Color::new(0.0, 0.0, 1.0, 1.0);
+
+    let palette = Color::quantize(&[red.clone(), lime.clone(), blue.clone()], 3);
+    assert_eq!(palette.len(), 3);
+    for rgba in [[255, 0, 0, 255], [0, 255, 0, 255], [0, 0, 255, 255]] {
+        assert!(palette.iter().any(|x| x.eq_rgba8(rgba)));
+    }
+
+    // more colors than requested
+    let colors = [
+        red.clone(),
+        Color::new(0.95, 0.02, 0.0, 1.0),
+        lime.clone(),
+        Color::new(0.0, 0.98, 0.03, 1.0),
+        blue.clone(),
+        Color::new(0.02, 0.0, 0.97, 1.0),
+    ];
+    let palette = Color::quantize(&colors, 3);
+    assert_eq!(palette.len(), 3);
+    for c in [&red, &lime, &blue] {
+        assert!(palette.iter().any(|x| x.hue_difference(c).abs() < 5.0));
+    }
+
+    // deterministic
+    assert_eq!(palette, Color::quantize(&colors, 3));
+
+    // not enough distinct colors
+    let palette = Color::quantize(&[red.clone(), red.clone(), red.clone()], 3);
+    assert_eq!(palette.len(), 1);
+    assert!(palette[0].eq_rgba8([255, 0, 0, 255]));
+
+    assert!(Color::quantize(&colors, 0).is_empty());
+    assert!(Color::quantize(&[], 3).is_empty());
+    assert_eq!(Color::quantize(&colors, 1).len(), 1);
+}
+
+#[test]
+fn over_composite() {
+    let base = Color::new(1.0, 1.0, 1.0, 1.0);
+    let tint = Color::new(1.0, 0.0, 0.0, 0.5);
+
+    assert_eq!(tint.over(&base).to_rgba8(), [255, 128, 128, 255]);
+    assert_eq!(base.over(&tint), base);
+    assert_eq!(
+        Color::composite(&[base.clone(), tint.clone()]),
+        tint.over(&base)
+    );
+
+    let top = Color::new(0.0, 0.0, 1.0, 0.25);
+    assert_eq!(
+        Color::composite(&[base.clone(), tint.clone(), top.clone()]),
+        top.over(&tint.over(&base))
+    );
+
+    // translucent over translucent
+    let c = tint.over(&Color::new(0.0, 0.0, 1.0, 0.5));
+    assert_eq!(c.to_rgba8(), [170, 0, 85, 191]);
+
+    let transparent = Color::new(0.0, 0.0, 0.0, 0.0);
+    assert_eq!(transparent.over(&transparent), transparent);
+    assert_eq!(Color::composite(&[]), transparent);
+    assert_eq!(Color::composite(std::slice::from_ref(&tint)), tint);
+}
+
+#[test]
+fn over_white_black() {
+    let red = Color::new(1.0, 0.0, 0.0, 0.5);
+    assert_eq!(red.over_white().to_rgba8(), [255, 128, 128, 255]);
+    assert_eq!(red.over_black().to_rgba8(), [128, 0, 0, 255]);
+
+    // Opaque colors are unchanged, transparent ones give the background
+    let c = Color::new(0.2, 0.4, 0.6, 1.0);
+    assert_eq!(c.over_white(), c);
+    assert_eq!(c.over_black(), c);
+    let transparent = Color::new(0.0, 0.0, 0.0, 0.0);
+    assert_eq!(transparent.over_white(), Color::new(1.0, 1.0, 1.0, 1.0));
+    assert_eq!(transparent.over_black(), Color::new(0.0, 0.0, 0.0, 1.0));
+}
+
+#[test]
+fn to_css_rgb_hsl_oklch() {
+    let data = [
+        (
+            Color::new(1.0, 0.0, 0.0, 1.0),
+            "rgb(255 0 0)",
+            "hsl(0 100% 50%)",
+            "oklch(0.628 0.2577 29.23)",
+        ),
+        (
+            Color::new(0.0, 0.0, 1.0, 0.5),
+            "rgb(0 0 255 / 50%)",
+            "hsl(240 100% 50% / 50%)",
+            "oklch(0.452 0.3132 264.05 / 50%)",
+        ),
+        (
+            Color::new(0.0, 0.0, 0.0, 1.0),
+            "rgb(0 0 0)",
+            "hsl(none 0% 0%)",
+            "oklch(0 0 none)",
+        ),
+        (
+            Color::new(0.5, 0.5, 0.5, 0.0),
+            "rgb(128 128 128 / 0%)",
+            "hsl(none 0% 50% / 0%)",
+            "oklch(0.5982 0 none / 0%)",
+        ),
+    ];
+    for (c, rgb, hsl, oklch) in data {
+        assert_eq!(c.to_css_rgb(), rgb);
+        assert_eq!(c.to_css_hsl(), hsl);
+        assert_eq!(c.to_css_oklch(), oklch);
+
+        for s in [rgb, hsl, oklch] {
+            assert_eq!(
+                csscolorparser::parse(s).unwrap().to_rgba8(),
+                c.to_rgba8(),
+                "{}",
+                s
+            );
+        }
+    }
+
+    // NaN channels are written as `none`
+    let c = Color::new(f32::NAN, 0.5, 1.0, 1.0);
+    assert_eq!(c.to_css_rgb(), "rgb(none 128 255)");
+    assert_eq!(
+        csscolorparser::parse(&c.to_css_rgb()).unwrap().to_rgba8(),
+        [0, 128, 255, 255]
+    );
+}
+
+#[test]
+fn interpolate_premultiplied_linear() {
+    let red = Color::new(1.0, 0.0, 0.0, 1.0);
+    let blue = Color::new(0.0, 0.0, 1.0, 1.0);
+    let transparent = Color::new(0.0, 0.0, 0.0, 0.0);
+
+    // Fading to transparent stays red, not darkened toward black
+    for t in [0.0, 0.25, 0.5, 0.75, 0.9] {
+        let c = red.interpolate_premultiplied_linear(&transparent, t);
+        assert_eq!(c.to_rgba8()[0..3], [255, 0, 0], "{}", t);
+        assert!((c.a - (1.0 - t)).abs() < 1e-6);
+    }
+    assert_eq!(
+        red.interpolate_rgb(&transparent, 0.5).to_rgba8(),
+        [128, 0, 0, 128]
+    );
+    assert_eq!(
+        red.interpolate_premultiplied_linear(&transparent, 1.0)
+            .to_rgba8(),
+        [0, 0, 0, 0]
+    );
+
+    // Opaque colors are interpolated in linear RGB, no gamma darkening at the midpoint
+    let c = red.interpolate_premultiplied_linear(&blue, 0.5);
+    assert_eq!(c.to_rgba8(), [188, 0, 188, 255]);
+    assert_eq!(
+        c.to_rgba8(),
+        red.interpolate_linear_rgb(&blue, 0.5).to_rgba8()
+    );
+
+    // Endpoints
+    let c1 = Color::new(0.2, 0.4, 0.6, 0.5);
+    let c2 = Color::new(0.9, 0.1, 0.35, 0.8);
+    assert_eq!(
+        c1.interpolate_premultiplied_linear(&c2, 0.0).to_rgba8(),
+        c1.to_rgba8()
+    );
+    assert_eq!(
+        c1.interpolate_premultiplied_linear(&c2, 1.0).to_rgba8(),
+        c2.to_rgba8()
+    );
+}
+
+#[test]
+fn interpolate_rgb_luma_preserving() {
+    let colors = [
+        Color::new(0.0, 0.0, 1.0, 1.0),
+        Color::new(1.0, 1.0, 0.0, 1.0),
+        Color::new(1.0, 0.0, 0.0, 1.0),
+        Color::new(0.0, 1.0, 0.0, 1.0),
+        Color::new(0.0, 0.0, 0.0, 1.0),
+        Color::new(1.0, 1.0, 1.0, 1.0),
+        Color::new(0.2, 0.6, 0.9, 1.0),
+        Color::new(0.9, 0.4, 0.1, 0.5),
+    ];
+    for c1 in &colors {
+        for c2 in &colors {
+            for t in [0.0, 0.25, 0.5, 0.75, 1.0] {
+                let c = c1.interpolate_rgb_luma_preserving(c2, t);
+                let expected = c1.luminance() + t * (c2.luminance() - c1.luminance());
+                assert!(
+                    (c.luminance() - expected).abs() < 1e-3,
+                    "{:?} {:?} {}",
+                    c1,
+                    c2,
+                    t
+                );
+                assert!([c.r, c.g, c.b].iter().all(|v| (0.0..=1.0).contains(v)));
+                assert!((c.a - (c1.a + t * (c2.a - c1.a))).abs() < 1e-6);
+            }
+        }
     }
+
+    // The midpoint is the average of the luminances, not darker as with interpolate_rgb()
+    let blue = &colors[0];
+    let yellow = &colors[1];
+    let avg = (blue.luminance() + yellow.luminance()) / 2.0;
+    let c = blue.interpolate_rgb_luma_preserving(yellow, 0.5);
+    assert!((c.luminance() - avg).abs() < 1e-4);
+    assert!(blue.interpolate_rgb(yellow, 0.5).luminance() < avg - 0.2);
+
+    // Endpoints
+    let c1 = &colors[6];
+    let c2 = &colors[7];
+    assert_eq!(
+        c1.interpolate_rgb_luma_preserving(c2, 0.0).to_rgba8(),
+        c1.to_rgba8()
+    );
+    assert_eq!(
+        c1.interpolate_rgb_luma_preserving(c2, 1.0).to_rgba8(),
+        c2.to_rgba8()
+    );
+}
+
+#[test]
+fn blend_with_alpha() {
+    let red = Color::new(1.0, 0.0, 0.0, 1.0);
+    let blue = Color::new(0.0, 0.0, 1.0, 0.25);
+
+    // The more opaque red contributes more to the RGB than with interpolate_rgb()
+    let c = red.blend_with_alpha(&blue, 0.5);
+    assert!((c.r - 0.8).abs() < 1e-6);
+    assert!((c.b - 0.2).abs() < 1e-6);
+    assert!((c.a - 0.625).abs() < 1e-6);
+    let c = red.interpolate_rgb(&blue, 0.5);
+    assert!((c.r - 0.5).abs() < 1e-6);
+    assert!((c.b - 0.5).abs() < 1e-6);
+    assert!((c.a - 0.625).abs() < 1e-6);
+    // over() composites instead, giving a different alpha
+    assert!((blue.over(&red).a - 1.0).abs() < 1e-6);
+
+    // Equal alphas are the same as interpolate_rgb()
+    let c1 = Color::new(0.2, 0.4, 0.6, 0.5);
+    let c2 = Color::new(0.9, 0.1, 0.35, 0.5);
+    assert_eq!(
+        c1.blend_with_alpha(&c2, 0.3).to_rgba8(),
+        c1.interpolate_rgb(&c2, 0.3).to_rgba8()
+    );
+
+    // Fading to transparent keeps the color
+    let transparent = Color::new(0.0, 0.0, 0.0, 0.0);
+    let c = red.blend_with_alpha(&transparent, 0.75);
+    assert_eq!(c.to_rgba8(), [255, 0, 0, 64]);
+    assert_eq!(
+        red.blend_with_alpha(&transparent, 1.0).to_rgba8(),
+        [0, 0, 0, 0]
+    );
+
+    // Endpoints
+    assert_eq!(red.blend_with_alpha(&blue, 0.0).to_rgba8(), red.to_rgba8());
+    assert_eq!(red.blend_with_alpha(&blue, 1.0).to_rgba8(), blue.to_rgba8());
+}
+
+#[test]
+fn rgba_f64() {
+    let data = [
+        [0.0, 0.0, 0.0, 0.0],
+        [1.0, 0.5, 0.25, 1.0],
+        [0.1, 0.2, 0.3, 0.4],
+        [-0.5, 1.5, 2.0, 0.7],
+    ];
+    for rgba in data {
+        let c = Color::from_rgba_f64(rgba);
+        assert_eq!(c.to_array(), rgba.map(|t| t as f32));
+        assert_eq!(Color::from_rgba_f64(c.to_rgba_f64()), c);
+    }
+
+    let c = Color::new(0.1, 0.2, 0.3, 0.4);
+    assert_eq!(
+        c.to_rgba_f64(),
+        [0.1f32 as f64, 0.2f32 as f64, 0.3f32 as f64, 0.4f32 as f64]
+    );
+}
+
+#[test]
+fn to_css_rgb_alpha_style() {
+    use csscolorparser::AlphaStyle;
+
+    assert_eq!(AlphaStyle::default(), AlphaStyle::Percentage);
+
+    let data = [
+        (1.0, "rgb(255 0 0)", "rgb(255 0 0)"),
+        (0.5, "rgb(255 0 0 / 50%)", "rgb(255 0 0 / 0.5)"),
+        (0.25, "rgb(255 0 0 / 25%)", "rgb(255 0 0 / 0.25)"),
+        (0.0, "rgb(255 0 0 / 0%)", "rgb(255 0 0 / 0)"),
+        (0.1234, "rgb(255 0 0 / 12.34%)", "rgb(255 0 0 / 0.1234)"),
+    ];
+    for (a, percentage, decimal) in data {
+        let c = Color::new(1.0, 0.0, 0.0, a);
+        assert_eq!(c.to_css_rgb(), percentage);
+        assert_eq!(c.to_css_rgb_with(AlphaStyle::Percentage), percentage);
+        assert_eq!(c.to_css_rgb_with(AlphaStyle::Decimal), decimal);
+
+        let c2 = csscolorparser::parse(decimal).unwrap();
+        assert!((c2.a - a).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn alpha_percentage_round_trip() {
+    for a in [0.0, 0.001, 0.1, 0.333, 1.0 / 3.0, 0.5, 0.66, 0.999, 1.0] {
+        let c = Color::from_rgba8(10, 20, 30, (a * 255.0_f32).round() as u8);
+        let c2 = csscolorparser::parse(&c.to_css_rgb()).unwrap();
+        assert_eq!(c2.to_rgba8(), c.to_rgba8(), "{}", c.to_css_rgb());
+
+        let c = Color::new(0.0, 0.5, 1.0, a);
+        for s in [c.to_css_rgb(), c.to_css_hsl(), c.to_css_oklch()] {
+            let c2 = csscolorparser::parse(&s).unwrap();
+            assert!((c2.a - a).abs() < 1e-4, "{}", s);
+        }
+    }
+
+    assert_eq!(
+        Color::new(1.0, 0.0, 0.0, 0.333).to_css_rgb(),
+        "rgb(255 0 0 / 33.3%)"
+    );
+}
+
+#[test]
+fn hsla_hsva_out_of_gamut() {
+    let c = Color::new(1.2, 0.5, -0.1, 1.0);
+
+    let [h, s, l, _] = c.to_hsla();
+    assert!((l - 0.55).abs() < 1e-6);
+    assert!(s > 1.0);
+    assert_eq!(c.clamp().to_hsla()[1..3], [1.0, 0.5]);
+    assert!((h - c.clamp().to_hsla()[0]).abs() > 1.0);
+
+    let [_, s, v, _] = c.to_hsva();
+    assert!(s > 1.0);
+    assert!((v - 1.2).abs() < 1e-6);
+    assert_eq!(c.clamp().to_hsva()[1..3], [1.0, 1.0]);
+}
+
+#[test]
+fn to_css_hsv() {
+    let data = [
+        (Color::new(1.0, 0.0, 0.0, 1.0), "hsv(0 100% 100%)"),
+        (Color::new(0.0, 1.0, 0.0, 0.5), "hsv(120 100% 100% / 50%)"),
+        (Color::new(0.0, 0.0, 0.0, 1.0), "hsv(none 0% 0%)"),
+        (Color::new(1.0, 1.0, 1.0, 1.0), "hsv(none 0% 100%)"),
+        (
+            Color::from_rgba8(64, 128, 192, 255),
+            "hsv(210 66.67% 75.29%)",
+        ),
+    ];
+    for (c, s) in data {
+        assert_eq!(c.to_css_hsv(), s);
+        assert_eq!(csscolorparser::parse(s).unwrap().to_rgba8(), c.to_rgba8());
+    }
+
+    for rgba in [[10, 200, 30, 255], [255, 128, 0, 100], [1, 2, 3, 4]] {
+        let c = Color::from_rgba8(rgba[0], rgba[1], rgba[2], rgba[3]);
+        let c2 = csscolorparser::parse(&c.to_css_hsv()).unwrap();
+        assert_eq!(c2.to_rgba8(), rgba, "{}", c.to_css_hsv());
+    }
+}
+
+#[test]
+fn color_builder() {
+    use csscolorparser::ColorBuilder;
+
+    let c = ColorBuilder::new().hsl(240.0, 1.0, 0.5).alpha(0.5).build();
+    assert_eq!(c.to_rgba8(), [0, 0, 255, 128]);
+
+    let c = ColorBuilder::new().alpha(0.25).rgb(1.0, 0.5, 0.0).build();
+    assert_eq!(c, Color::new(1.0, 0.5, 0.0, 0.25));
+
+    // Later color space overrides earlier one
+    let c = ColorBuilder::new()
+        .rgb(1.0, 0.0, 0.0)
+        .hsl(120.0, 1.0, 0.5)
+        .build();
+    assert_eq!(c.to_rgba8(), [0, 255, 0, 255]);
+    let c = ColorBuilder::new()
+        .hsl(120.0, 1.0, 0.5)
+        .rgb(1.0, 0.0, 0.0)
+        .build();
+    assert_eq!(c.to_rgba8(), [255, 0, 0, 255]);
+
+    assert_eq!(ColorBuilder::new().build(), Color::new(0.0, 0.0, 0.0, 1.0));
+    assert_eq!(ColorBuilder::default(), ColorBuilder::new());
+}
+
+#[test]
+fn srgb_gamut() {
+    let in_gamut = |c: &Color| [c.r, c.g, c.b].iter().all(|t| (0.0..=1.0).contains(t));
+
+    // High chroma Oklch colors are out of gamut
+    for hue in [0.0_f32, 30.0, 90.0, 150.0, 210.0, 270.0, 330.0] {
+        let h = hue.to_radians();
+        let raw = Color::from_oklcha(0.7, 0.4, h, 0.5);
+        assert!(!in_gamut(&raw));
+
+        let c = Color::from_oklcha_gamut(0.7, 0.4, h, 0.5);
+        assert!(in_gamut(&c), "{:?}", c);
+        assert_eq!(c.a, 0.5);
+        assert_eq!(c, raw.to_srgb_gamut());
+
+        // Lightness and hue are (nearly) preserved, chroma is reduced
+        let [l2, c2, h2, _] = c.to_oklcha();
+        assert!((l2 - 0.7).abs() < 0.02, "{} {}", hue, l2);
+        assert!(c2 < 0.4);
+        let dh = (h2 - h).sin().abs();
+        assert!(dh < 0.1, "{} {}", hue, h2.to_degrees());
+    }
+
+    // In gamut colors are unchanged
+    for c in [
+        Color::new(1.0, 0.0, 0.0, 1.0),
+        Color::new(0.2, 0.4, 0.6, 0.8),
+        Color::new(1.0, 1.0, 1.0, 1.0),
+        Color::new(0.0, 0.0, 0.0, 0.0),
+    ] {
+        assert_eq!(c.to_srgb_gamut(), c);
+    }
+
+    // Lightness out of range
+    assert_eq!(
+        Color::from_oklcha_gamut(1.2, 0.1, 1.0, 1.0),
+        Color::new(1.0, 1.0, 1.0, 1.0)
+    );
+    assert_eq!(
+        Color::from_oklcha_gamut(-0.1, 0.1, 1.0, 1.0),
+        Color::new(0.0, 0.0, 0.0, 1.0)
+    );
+}
+
+#[test]
+fn srgb_gamut_with() {
+    use csscolorparser::GamutMapMethod;
+
+    for hue in [0.0_f32, 30.0, 90.0, 150.0, 210.0, 270.0, 330.0] {
+        let h = hue.to_radians();
+        let raw = Color::from_oklcha(0.7, 0.4, h, 0.5);
+
+        let clip = raw.to_srgb_gamut_with(GamutMapMethod::Clip);
+        let chroma = raw.to_srgb_gamut_with(GamutMapMethod::OklchChromaReduction);
+        let hybrid = raw.to_srgb_gamut_with(GamutMapMethod::ClipChromaHybrid);
+        assert_eq!(clip, raw.clamp());
+        assert_eq!(hybrid, raw.to_srgb_gamut());
+        for c in [&clip, &chroma, &hybrid] {
+            assert!([c.r, c.g, c.b].iter().all(|t| (0.0..=1.0).contains(t)));
+            assert_eq!(c.a, 0.5);
+        }
+
+        // Chroma reduction keeps lightness and hue, clipping does not
+        let [l1, c1, h1, _] = chroma.to_oklcha();
+        assert!((l1 - 0.7).abs() < 1e-3, "{} {}", hue, l1);
+        assert!((h1 - h).sin().abs() < 1e-3, "{} {}", hue, h1.to_degrees());
+        let [l2, c2, h2, _] = clip.to_oklcha();
+        assert!(
+            (l2 - 0.7).abs() > 1e-3 || (h2 - h).sin().abs() > 1e-3,
+            "{}",
+            hue
+        );
+
+        // Pure chroma reduction is the least colorful, clipping the most
+        let [_, c3, _, _] = hybrid.to_oklcha();
+        assert!(c1 <= c3 + 1e-4, "{} {} {}", hue, c1, c3);
+        assert!(c3 <= c2 + 1e-4, "{} {} {}", hue, c3, c2);
+    }
+
+    let c = Color::new(0.2, 0.4, 0.6, 0.8);
+    for method in [
+        GamutMapMethod::Clip,
+        GamutMapMethod::OklchChromaReduction,
+        GamutMapMethod::ClipChromaHybrid,
+    ] {
+        assert_eq!(c.to_srgb_gamut_with(method), c);
+    }
+    assert_eq!(GamutMapMethod::default(), GamutMapMethod::ClipChromaHybrid);
+}
+
+#[test]
+fn to_css_hex_with() {
+    use csscolorparser::{AlphaMode, HexOptions};
+
+    let opaque = Color::from_rgba8(255, 170, 0, 255);
+    let opaque_long = Color::from_rgba8(255, 171, 0, 255);
+    let alpha = Color::from_rgba8(255, 170, 0, 136);
+    let alpha_long = Color::from_rgba8(255, 170, 0, 128);
+
+    assert_eq!(opaque.to_css_hex(), "#ffaa00");
+    assert_eq!(alpha.to_css_hex(), "#ffaa0088");
+    assert_eq!(opaque.to_css_hex(), opaque.to_hex_string());
+    assert_eq!(alpha.to_css_hex(), alpha.to_hex_string());
+
+    #[rustfmt::skip]
+    let data = [
+        // uppercase, short, alpha, [opaque, opaque_long, alpha, alpha_long]
+        (false, false, AlphaMode::Auto, ["#ffaa00", "#ffab00", "#ffaa0088", "#ffaa0080"]),
+        (true, false, AlphaMode::Auto, ["#FFAA00", "#FFAB00", "#FFAA0088", "#FFAA0080"]),
+        (false, true, AlphaMode::Auto, ["#fa0", "#ffab00", "#fa08", "#ffaa0080"]),
+        (true, true, AlphaMode::Auto, ["#FA0", "#FFAB00", "#FA08", "#FFAA0080"]),
+        (false, false, AlphaMode::Always, ["#ffaa00ff", "#ffab00ff", "#ffaa0088", "#ffaa0080"]),
+        (true, false, AlphaMode::Always, ["#FFAA00FF", "#FFAB00FF", "#FFAA0088", "#FFAA0080"]),
+        (false, true, AlphaMode::Always, ["#fa0f", "#ffab00ff", "#fa08", "#ffaa0080"]),
+        (true, true, AlphaMode::Always, ["#FA0F", "#FFAB00FF", "#FA08", "#FFAA0080"]),
+        (false, false, AlphaMode::Never, ["#ffaa00", "#ffab00", "#ffaa00", "#ffaa00"]),
+        (true, false, AlphaMode::Never, ["#FFAA00", "#FFAB00", "#FFAA00", "#FFAA00"]),
+        (false, true, AlphaMode::Never, ["#fa0", "#ffab00", "#fa0", "#fa0"]),
+        (true, true, AlphaMode::Never, ["#FA0", "#FFAB00", "#FA0", "#FA0"]),
+    ];
+
+    for (uppercase, short, alpha_mode, expected) in data {
+        let opts = HexOptions {
+            uppercase,
+            short,
+            alpha: alpha_mode,
+        };
+        for (c, s) in [&opaque, &opaque_long, &alpha, &alpha_long]
+            .iter()
+            .zip(expected)
+        {
+            assert_eq!(c.to_css_hex_with(opts), s, "{:?}", opts);
+            if alpha_mode != AlphaMode::Never {
+                assert_eq!(csscolorparser::parse(s).unwrap().to_rgba8(), c.to_rgba8());
+            }
+        }
+    }
+}
+
+#[cfg(feature = "fast-linear")]
+#[test]
+fn linear_rgba_fast() {
+    for i in 0..=1000 {
+        let t = i as f32 / 1000.0;
+        let c = Color::new(t, 1.0 - t, t * 0.5, 0.5);
+        let exact = c.to_linear_rgba();
+        let fast = c.to_linear_rgba_fast();
+        for (a, b) in exact.iter().zip(fast) {
+            assert!((a - b).abs() < 0.0017, "{} {} {}", t, a, b);
+        }
+        assert_eq!(fast[3], 0.5);
+    }
+
+    for i in 0..=255 {
+        let c = Color::from_rgba8(i, i, i, 255);
+        let exact = (c.to_linear_rgba()[0] * 255.0).round() as i32;
+        let fast = (c.to_linear_rgba_fast()[0] * 255.0).round() as i32;
+        assert!((exact - fast).abs() <= 1, "{}", i);
+    }
+
+    assert_eq!(
+        Color::new(0.0, 1.0, 0.0, 1.0).to_linear_rgba_fast()[0..2],
+        [0.0, 1.0]
+    );
+}
+
+#[test]
+fn lighten_darken() {
+    let c = csscolorparser::parse("#3366cc").unwrap();
+    let [l1, c1, h1, a1] = c.to_oklcha();
+
+    let x = c.lighten(0.1);
+    let [l2, c2, h2, a2] = x.to_oklcha();
+    assert!((l2 - (l1 + 0.1)).abs() < 1e-4);
+    assert!((c2 - c1).abs() < 1e-4);
+    assert!((h2 - h1).abs() < 1e-3);
+    assert_eq!(a1, a2);
+
+    let x = c.darken(0.1);
+    let [l2, c2, h2, _] = x.to_oklcha();
+    assert!((l2 - (l1 - 0.1)).abs() < 1e-4);
+    assert!((c2 - c1).abs() < 1e-4);
+    assert!((h2 - h1).abs() < 1e-3);
+    assert_eq!(c.darken(0.1).to_rgba8(), c.lighten(-0.1).to_rgba8());
+
+    // Saturates at the extremes, doesn't wrap
+    let white = Color::new(1.0, 1.0, 1.0, 0.5);
+    let black = Color::new(0.0, 0.0, 0.0, 0.5);
+    assert_eq!(white.lighten(0.5).to_rgba8(), [255, 255, 255, 128]);
+    assert_eq!(black.darken(0.5).to_rgba8(), [0, 0, 0, 128]);
+    // Chroma is kept, so these are not white and black
+    assert!((c.lighten(2.0).lightness_oklch() - 1.0).abs() < 1e-4);
+    assert!(c.darken(2.0).lightness_oklch().abs() < 1e-4);
+
+    // Chroma and hue are kept even out of the sRGB gamut
+    let blue = Color::new(0.0, 0.0, 1.0, 1.0);
+    let [_, c1, h1, _] = blue.to_oklcha();
+    let [_, c2, h2, _] = blue.lighten(0.3).to_oklcha();
+    assert!((c2 - c1).abs() < 1e-3);
+    assert!((h2 - h1).abs() < 1e-3);
+}
+
+#[test]
+fn lighten_darken_in_gamut() {
+    let c = csscolorparser::parse("#3366cc").unwrap();
+    assert_eq!(
+        c.lighten_in_gamut(0.1).to_rgba8(),
+        c.lighten(0.1).to_rgba8()
+    );
+    assert_eq!(c.darken_in_gamut(0.1).to_rgba8(), c.darken(0.1).to_rgba8());
+
+    // The extremes are white and black
+    assert_eq!(c.lighten_in_gamut(2.0).to_rgba8(), [255, 255, 255, 255]);
+    assert_eq!(c.darken_in_gamut(2.0).to_rgba8(), [0, 0, 0, 255]);
+
+    // The result is always in the sRGB gamut
+    let c = Color::new(0.0, 0.0, 1.0, 0.5).lighten_in_gamut(0.3);
+    assert!([c.r, c.g, c.b].iter().all(|t| (0.0..=1.0).contains(t)));
+    assert_eq!(c.a, 0.5);
+}
+
+#[test]
+fn whiten_blacken() {
+    let red = Color::new(1.0, 0.0, 0.0, 0.5);
+
+    // More whiteness moves toward white
+    let mut prev = red.clone();
+    for _ in 0..4 {
+        let c = prev.whiten(0.25);
+        assert!(c.g > prev.g && c.b > prev.b);
+        assert_eq!(c.r, 1.0);
+        assert_eq!(c.a, 0.5);
+        prev = c;
+    }
+    assert_eq!(prev.to_rgba8(), [255, 255, 255, 128]);
+    assert_eq!(red.whiten(2.0).to_rgba8(), [255, 255, 255, 128]);
+
+    let c = red.blacken(0.5);
+    assert_eq!(c.to_rgba8(), [128, 0, 0, 128]);
+    assert_eq!(red.blacken(1.0).to_rgba8(), [0, 0, 0, 128]);
+
+    // Hue is kept
+    let c = Color::from_hwba(200.0, 0.1, 0.2, 1.0)
+        .whiten(0.2)
+        .blacken(0.1);
+    let [h, w, b, _] = c.to_hwba();
+    assert!((h - 200.0).abs() < 0.01);
+    assert!((w - 0.3).abs() < 1e-5);
+    assert!((b - 0.3).abs() < 1e-5);
+
+    // Negative amounts, clamped to 0
+    let c = Color::from_hwba(120.0, 0.2, 0.2, 1.0)
+        .whiten(-0.5)
+        .blacken(-0.5);
+    assert_eq!(c.to_rgba8(), [0, 255, 0, 255]);
+
+    // Whiteness plus blackness over 1 is a gray
+    let c = Color::from_hwba(0.0, 0.6, 0.2, 1.0).blacken(0.4);
+    assert_eq!(c.to_rgba8(), [128, 128, 128, 255]);
+}
+
+#[test]
+fn saturate_relative() {
+    let c = Color::from_hsla(200.0, 0.5, 0.4, 0.8);
+
+    // Relative: +20% of the current saturation
+    let [h, s, l, a] = c.saturate_relative(1.2).to_hsla();
+    assert!((h - 200.0).abs() < 1e-3);
+    assert!((s - 0.6).abs() < 1e-5);
+    assert!((l - 0.4).abs() < 1e-5);
+    assert_eq!(a, 0.8);
+
+    // Absolute: +0.2 of saturation, a different result
+    let absolute = Color::from_hsla(200.0, 0.5 + 0.2, 0.4, 0.8);
+    assert!((absolute.to_hsla()[1] - 0.7).abs() < 1e-5);
+    assert_ne!(c.saturate_relative(1.2).to_rgba8(), absolute.to_rgba8());
+
+    let s = c.saturate_relative(0.5).to_hsla()[1];
+    assert!((s - 0.25).abs() < 1e-5);
+    assert!((c.saturate_relative(3.0).to_hsla()[1] - 1.0).abs() < 1e-5);
+    assert_eq!(c.saturate_relative(0.0).to_hsla()[1], 0.0);
+    assert!((c.saturate_relative(-1.0).to_hsla()[1]).abs() < 1e-6);
+
+    // Gray stays gray
+    let gray = Color::new(0.5, 0.5, 0.5, 1.0);
+    assert_eq!(gray.saturate_relative(2.0).to_rgba8(), gray.to_rgba8());
+}
+
+#[test]
+fn to_array_raw_clamped() {
+    let c = Color::new(0.2, 0.4, 0.6, 0.8);
+    assert_eq!(c.to_array(), [0.2, 0.4, 0.6, 0.8]);
+    assert_eq!(c.to_array_raw(), [0.2, 0.4, 0.6, 0.8]);
+    assert_eq!(c.to_array_clamped(), [0.2, 0.4, 0.6, 0.8]);
+
+    let c = Color::new(1.5, -0.5, 0.5, 2.0);
+    assert_eq!(c.to_array(), [1.5, -0.5, 0.5, 2.0]);
+    assert_eq!(c.to_array_raw(), [1.5, -0.5, 0.5, 2.0]);
+    assert_eq!(c.to_array_clamped(), [1.0, 0.0, 0.5, 1.0]);
+}
+
+#[test]
+fn to_ansi256() {
+    let test_data = [
+        ([0, 0, 0], 16),
+        ([255, 255, 255], 231),
+        ([255, 0, 0], 196),
+        ([0, 255, 0], 46),
+        ([0, 0, 255], 21),
+        ([255, 255, 0], 226),
+        ([0, 255, 255], 51),
+        ([255, 0, 255], 201),
+        ([95, 135, 175], 67),
+        ([128, 128, 128], 244),
+        ([8, 8, 8], 232),
+        ([238, 238, 238], 255),
+        ([100, 102, 98], 241),
+        ([250, 10, 5], 196),
+    ];
+    for ([r, g, b], idx) in test_data {
+        assert_eq!(
+            Color::from_rgba8(r, g, b, 255).to_ansi256(),
+            idx,
+            "{:?}",
+            [r, g, b]
+        );
+    }
+
+    // Alpha is ignored
+    assert_eq!(Color::new(1.0, 0.0, 0.0, 0.0).to_ansi256(), 196);
+}
+
+#[test]
+fn from_ansi256() {
+    let test_data = [
+        (0, "#000000"),
+        (1, "#800000"),
+        (7, "#c0c0c0"),
+        (8, "#808080"),
+        (9, "#ff0000"),
+        (15, "#ffffff"),
+        (16, "#000000"),
+        (21, "#0000ff"),
+        (46, "#00ff00"),
+        (67, "#5f87af"),
+        (196, "#ff0000"),
+        (231, "#ffffff"),
+        (232, "#080808"),
+        (244, "#808080"),
+        (255, "#eeeeee"),
+    ];
+    for (idx, hex) in test_data {
+        assert_eq!(Color::from_ansi256(idx).to_hex_string(), hex, "{}", idx);
+    }
+
+    // Round trip for the color cube and the grayscale ramp
+    for idx in 16..=255 {
+        assert_eq!(Color::from_ansi256(idx).to_ansi256(), idx);
+    }
+}
+
+#[test]
+fn to_ansi_truecolor_escape() {
+    let c = Color::from_rgba8(255, 128, 0, 100);
+    assert_eq!(c.to_ansi_truecolor_escape(), "\x1b[38;2;255;128;0m");
+    assert_eq!(
+        Color::new(0.0, 0.0, 0.0, 1.0).to_ansi_truecolor_escape(),
+        "\x1b[38;2;0;0;0m"
+    );
+}
+
+#[test]
+fn mix_subtractive() {
+    let red = Color::new(1.0, 0.0, 0.0, 1.0);
+    let yellow = Color::new(1.0, 1.0, 0.0, 1.0);
+    let blue = Color::new(0.0, 0.0, 1.0, 0.5);
+
+    // Blue and yellow make green, not gray
+    let c = blue.mix_subtractive(&yellow, 0.5);
+    assert!(c.g > c.r && c.g > c.b);
+    let [_, s, _, _] = c.to_hsla();
+    assert!(s > 0.5);
+    assert_eq!(c.a, 0.75);
+
+    // Red and blue make purple
+    let [h, _, _, _] = red.mix_subtractive(&blue, 0.5).to_hsla();
+    assert!((h - 300.0).abs() < 1.0);
+
+    // Red and yellow make orange
+    let [h, _, _, _] = red.mix_subtractive(&yellow, 0.5).to_hsla();
+    assert!((h - 30.0).abs() < 1.0);
+
+    // Endpoints
+    assert_eq!(
+        blue.mix_subtractive(&yellow, 0.0).to_rgba8(),
+        blue.to_rgba8()
+    );
+    assert_eq!(
+        blue.mix_subtractive(&yellow, 1.0).to_rgba8(),
+        yellow.to_rgba8()
+    );
+}
+
+#[test]
+fn round_to_rgba8() {
+    let colors = [
+        Color::new(0.2, 0.4, 0.6, 0.8),
+        Color::new(0.50001, 0.49999, 0.123, 1.0),
+        Color::new(1.5, -0.5, 0.5, 2.0),
+        Color::from_oklcha(0.7, 0.1, 2.0, 1.0),
+    ];
+    for c in colors {
+        let r = c.round_to_rgba8();
+        assert_eq!(r.to_rgba8(), c.to_rgba8());
+        assert_eq!(r.round_to_rgba8(), r);
+        assert_eq!(r.to_css_oklch(), r.round_to_rgba8().to_css_oklch());
+    }
+
+    // Tiny float noise doesn't change the serialization
+    let a = Color::new(0.5, 0.25, 0.75, 1.0).round_to_rgba8();
+    let b = Color::new(0.5 + 1e-4, 0.25 - 1e-4, 0.75, 1.0).round_to_rgba8();
+    assert_eq!(a, b);
+    assert_eq!(a.to_css_oklch(), b.to_css_oklch());
+    assert_eq!(a.to_css_hsl(), b.to_css_hsl());
+}
+
+#[test]
+fn tuple_accessors() {
+    let colors = [
+        Color::new(1.0, 0.0, 0.0, 1.0),
+        Color::new(0.2, 0.4, 0.6, 0.8),
+        Color::new(0.5, 0.5, 0.5, 0.0),
+    ];
+    for c in colors {
+        let [h, s, v, a] = c.to_hsva();
+        assert_eq!(c.to_hsv_tuple(), (h, s, v, a));
+
+        let [h, s, l, a] = c.to_hsla();
+        assert_eq!(c.to_hsl_tuple(), (h, s, l, a));
+
+        let [h, w, b, a] = c.to_hwba();
+        assert_eq!(c.to_hwb_tuple(), (h, w, b, a));
+    }
+
+    assert_eq!(
+        Color::new(0.0, 0.0, 1.0, 1.0).to_hsl_tuple(),
+        (240.0, 1.0, 0.5, 1.0)
+    );
+}
+
+#[test]
+fn to_css_best() {
+    use csscolorparser::parse;
+
+    // In gamut
+    for s in ["#ff0000", "#bad455", "#00000080", "#ffffff"] {
+        assert_eq!(parse(s).unwrap().to_css_best(), s);
+    }
+    let c = parse("oklch(0.6 0.05 200)").unwrap();
+    assert_eq!(c.to_css_best(), "#5c8a8c");
+
+    // Out of gamut
+    for s in [
+        "oklch(0.87 0.35 142)",
+        "oklch(0.5 0.3 264 / 50%)",
+        "oklab(0.6 -0.3 0.2)",
+    ] {
+        let c = parse(s).unwrap();
+        let best = c.to_css_best();
+        assert!(best.starts_with("oklch("), "{} {}", s, best);
+        let c2 = parse(&best).unwrap();
+        for (a, b) in c.to_oklcha().iter().zip(c2.to_oklcha()) {
+            assert!((a - b).abs() < 1e-3, "{} {}", s, best);
+        }
+    }
+    assert_eq!(
+        parse("oklch(0.87 0.35 142)").unwrap().to_css_best(),
+        "oklch(0.87 0.35 142)"
+    );
+}
+
+#[test]
+fn to_css_minimal() {
+    let test_data = [
+        ([255, 255, 0, 255], "#ff0"),
+        ([255, 255, 255, 255], "#fff"),
+        ([0, 0, 0, 255], "#000"),
+        ([0, 255, 255, 255], "#0ff"),
+        ([18, 52, 86, 255], "#123456"),
+        ([255, 0, 0, 136], "#f008"),
+        ([0, 0, 255, 128], "#0000ff80"),
+        ([0, 0, 0, 0], "#0000"),
+    ];
+    for (rgba, expected) in test_data {
+        let [r, g, b, a] = rgba;
+        let c = Color::from_rgba8(r, g, b, a);
+        let s = c.to_css_minimal();
+        assert_eq!(s, expected);
+        assert!(s.len() <= c.to_css_hex().len());
+        assert!(s.len() < c.to_css_rgb().len());
+        assert_eq!(csscolorparser::parse(&s).unwrap().to_rgba8(), rgba);
+    }
+
+    #[cfg(feature = "named-colors")]
+    {
+        let test_data = [
+            ([255, 0, 0, 255], "red"),
+            ([210, 180, 140, 255], "tan"),
+            ([128, 128, 128, 255], "gray"),
+            ([128, 0, 0, 255], "maroon"),
+            ([0, 0, 128, 255], "navy"),
+            ([0, 128, 0, 255], "green"),
+            // Not shorter than the hex
+            ([255, 255, 255, 255], "#fff"),
+            ([0, 255, 255, 255], "#0ff"),
+            ([255, 0, 255, 255], "#f0f"),
+            // Names are only used for opaque colors
+            ([255, 0, 0, 128], "#ff000080"),
+        ];
+        for ([r, g, b, a], expected) in test_data {
+            assert_eq!(Color::from_rgba8(r, g, b, a).to_css_minimal(), expected);
+        }
+    }
+}
+
+#[test]
+fn from_gray() {
+    assert_eq!(Color::from_grayscale(0.5), Color::new(0.5, 0.5, 0.5, 1.0));
+    assert_eq!(
+        Color::from_grayscale_alpha(0.25, 0.5),
+        Color::new(0.25, 0.25, 0.25, 0.5)
+    );
+    assert_eq!(Color::from_gray_u8(128).to_rgba8(), [128, 128, 128, 255]);
+    assert_eq!(Color::from_gray_alpha_u8(0, 100).to_rgba8(), [0, 0, 0, 100]);
+    assert_eq!(Color::from_gray_u8(255), Color::from_grayscale(1.0));
+
+    let [_, s, _, _] = Color::from_grayscale(0.3).to_hsla();
+    assert_eq!(s, 0.0);
+}
+
+#[test]
+fn to_css_rgb_raw() {
+    let c = Color::new(1.0, 0.5, 0.0, 1.0);
+    assert_eq!(c.to_css_rgb_raw(), "rgb(255 127.5 0)");
+
+    let c = Color::new(0.2, 0.4, 0.6, 0.5);
+    assert_eq!(c.to_css_rgb_raw(), "rgb(51 102 153 / 50%)");
+
+    // Out of gamut
+    let c = csscolorparser::parse("oklch(0.87 0.35 142)").unwrap();
+    let raw = c.to_css_rgb_raw();
+    let channels = raw
+        .trim_start_matches("rgb(")
+        .trim_end_matches(')')
+        .split(' ')
+        .map(|s| s.parse::<f32>().unwrap())
+        .collect::<Vec<_>>();
+    assert!(channels[0] < 0.0);
+    assert!(channels[1] > 255.0);
+    assert!(channels[2] < 0.0);
+    assert_eq!(c.to_css_rgb(), c.clamp().to_css_rgb());
+
+    let c = Color::new(f32::NAN, 0.0, 1.5, 1.0);
+    assert_eq!(c.to_css_rgb_raw(), "rgb(none 0 382.5)");
+}
+
+#[test]
+fn components_from_iter() {
+    let c = Color::new(0.1, 0.2, 0.3, 0.4);
+    assert_eq!(c.components().collect::<Vec<_>>(), [0.1, 0.2, 0.3, 0.4]);
+    assert_eq!(c.components().collect::<Color>(), c);
+
+    let colors = [c.clone(), Color::new(1.5, -0.5, 0.0, 1.0)];
+    let buf = colors
+        .iter()
+        .flat_map(Color::components)
+        .collect::<Vec<_>>();
+    assert_eq!(buf, [0.1, 0.2, 0.3, 0.4, 1.5, -0.5, 0.0, 1.0]);
+
+    let colors2 = buf
+        .chunks(4)
+        .map(|ch| ch.iter().copied().collect::<Color>())
+        .collect::<Vec<_>>();
+    assert_eq!(colors2, colors);
+
+    // Missing alpha is 1, missing channels are 0, extra values are ignored
+    let data = [
+        (vec![1.0, 0.5, 0.25], Color::new(1.0, 0.5, 0.25, 1.0)),
+        (vec![1.0], Color::new(1.0, 0.0, 0.0, 1.0)),
+        (vec![], Color::new(0.0, 0.0, 0.0, 1.0)),
+        (
+            vec![0.1, 0.2, 0.3, 0.4, 0.5],
+            Color::new(0.1, 0.2, 0.3, 0.4),
+        ),
+    ];
+    for (v, expected) in data {
+        assert_eq!(v.into_iter().collect::<Color>(), expected);
+    }
+}
+
+#[test]
+fn arithmetic_ops() {
+    let a = Color::new(0.5, 0.25, 1.0, 1.0);
+    let b = Color::new(0.25, 0.5, 0.5, 0.5);
+
+    assert_eq!(a.clone() + b.clone(), Color::new(0.75, 0.75, 1.5, 1.5));
+    assert_eq!(a.clone() - b.clone(), Color::new(0.25, -0.25, 0.5, 0.5));
+    assert_eq!(a.clone() * 2.0, Color::new(1.0, 0.5, 2.0, 2.0));
+    assert_eq!(a.clone() * 0.0, Color::new(0.0, 0.0, 0.0, 0.0));
+
+    // Blending
+    let mid = a.clone() * 0.5 + b.clone() * 0.5;
+    assert_eq!(mid, Color::new(0.375, 0.375, 0.75, 0.75));
+    assert_eq!(mid, a.interpolate_rgb(&b, 0.5));
+
+    assert_eq!(a.clone() - a.clone(), Color::new(0.0, 0.0, 0.0, 0.0));
+}
+
+#[test]
+fn interpolate_eased() {
+    use csscolorparser::{ease_in, ease_in_out, ease_out, InterpSpace};
+
+    for ease in [ease_in, ease_out, ease_in_out] {
+        assert_eq!(ease(0.0), 0.0);
+        assert_eq!(ease(1.0), 1.0);
+    }
+    assert_eq!(ease_in_out(0.5), 0.5);
+    assert!(ease_in(0.5) < 0.5);
+    assert!(ease_out(0.5) > 0.5);
+    assert!(ease_in_out(0.25) < 0.25);
+    assert!(ease_in_out(0.75) > 0.75);
+
+    let a = Color::new(0.0, 0.0, 0.0, 1.0);
+    let b = Color::new(1.0, 1.0, 1.0, 1.0);
+    let linear = |t| t;
+
+    let spaces = [
+        InterpSpace::Rgb,
+        InterpSpace::LinearRgb,
+        InterpSpace::Hsv,
+        InterpSpace::Oklab,
+        InterpSpace::Lab,
+        InterpSpace::Lch,
+    ];
+    for space in spaces {
+        // Endpoints are kept
+        assert!(a.interpolate_eased(&b, 0.0, space, ease_in).equals_css(&a));
+        assert!(b.interpolate_eased(&a, 1.0, space, ease_in).equals_css(&a));
+
+        // Easing changes the midpoint relative to linear t
+        let mid = a.interpolate_eased(&b, 0.5, space, linear);
+        let mid_in = a.interpolate_eased(&b, 0.5, space, ease_in);
+        let mid_out = a.interpolate_eased(&b, 0.5, space, ease_out);
+        assert!(mid_in.r < mid.r, "{:?}", space);
+        assert!(mid_out.r > mid.r, "{:?}", space);
+    }
+
+    assert_eq!(
+        a.interpolate_eased(&b, 0.5, InterpSpace::Rgb, linear),
+        a.interpolate_rgb(&b, 0.5)
+    );
+}
+
+#[test]
+fn u32_argb() {
+    let c = Color::from_u32_argb(0x80ff0000);
+    assert_eq!(c.to_rgba8(), [255, 0, 0, 128]);
+    assert_eq!(c.to_css_hex(), "#ff000080");
+    assert_eq!(c.to_argb_hex(), "#80ff0000");
+
+    assert_eq!(
+        Color::from_u32_argb(0xff00ff00).to_rgba8(),
+        [0, 255, 0, 255]
+    );
+    assert_eq!(Color::from_u32_argb(0).to_rgba8(), [0, 0, 0, 0]);
+    assert_eq!(Color::new(0.0, 0.0, 1.0, 1.0).to_u32_argb(), 0xff0000ff);
+
+    for argb in [0x80ff0000, 0x12345678, 0xffffffff, 0x00abcdef, 0] {
+        assert_eq!(Color::from_u32_argb(argb).to_u32_argb(), argb);
+    }
+}
+
+#[test]
+fn pick_highest_contrast() {
+    let base = Color::from_rgba8(34, 34, 34, 255);
+    let candidates = [
+        Color::from_rgba8(85, 85, 85, 255),
+        Color::from_rgba8(153, 153, 153, 255),
+        Color::from_rgba8(255, 255, 255, 255),
+    ];
+
+    // Highest contrast
+    let c = Color::pick_highest_contrast(&base, &candidates, None);
+    assert!(c.eq_rgba8([255, 255, 255, 255]));
+
+    // First one reaching the target
+    let c = Color::pick_highest_contrast(&base, &candidates, Some(4.5));
+    assert!(c.eq_rgba8([153, 153, 153, 255]));
+    let c = Color::pick_highest_contrast(&base, &candidates, Some(1.0));
+    assert!(c.eq_rgba8([85, 85, 85, 255]));
+
+    // None reaching the target, the highest contrast is used
+    let c = Color::pick_highest_contrast(&base, &candidates, Some(21.0));
+    assert!(c.eq_rgba8([255, 255, 255, 255]));
+
+    // Light base
+    let base = Color::from_rgba8(238, 238, 238, 255);
+    let c = Color::pick_highest_contrast(&base, &candidates, None);
+    assert!(c.eq_rgba8([85, 85, 85, 255]));
+
+    // First one on a tie
+    let candidates = [
+        Color::from_rgba8(0, 0, 255, 255),
+        Color::from_rgba8(0, 0, 255, 128),
+    ];
+    let c = Color::pick_highest_contrast(&base, &candidates, None);
+    assert!(c.eq_rgba8([0, 0, 255, 255]));
+
+    // Black or white if there are no candidates
+    let c = Color::pick_highest_contrast(&base, &[], None);
+    assert!(c.eq_rgba8([0, 0, 0, 255]));
+    let c = Color::pick_highest_contrast(&Color::new(0.1, 0.1, 0.3, 1.0), &[], None);
+    assert!(c.eq_rgba8([255, 255, 255, 255]));
+}
+
+#[test]
+fn delta_e_2000() {
+    let a = Color::from_rgba8(200, 100, 50, 255);
+    assert_eq!(a.delta_e_2000(&a), 0.0);
+
+    let black = Color::new(0.0, 0.0, 0.0, 1.0);
+    let white = Color::new(1.0, 1.0, 1.0, 1.0);
+    assert!((black.delta_e_2000(&white) - 100.0).abs() < 0.01);
+    assert!((white.delta_e_2000(&black) - 100.0).abs() < 0.01);
+
+    // Below the JND
+    let b = Color::from_rgba8(201, 101, 50, 255);
+    assert!(a.delta_e_2000(&b) < 2.3);
+    assert!(a.visually_equal(&b, 2.3));
+
+    // Above the JND
+    let b = Color::from_rgba8(200, 100, 70, 255);
+    assert!(a.delta_e_2000(&b) > 2.3);
+    assert!(!a.visually_equal(&b, 2.3));
+    assert!(a.visually_equal(&b, 10.0));
+
+    // Alpha is ignored
+    let b = Color::from_rgba8(200, 100, 50, 0);
+    assert_eq!(a.delta_e_2000(&b), 0.0);
+    assert!(a.visually_equal(&b, 2.3));
+}
+
+#[test]
+fn delta_e_ok_and_76() {
+    let black = Color::new(0.0, 0.0, 0.0, 1.0);
+    let white = Color::new(1.0, 1.0, 1.0, 1.0);
+    let red = Color::new(1.0, 0.0, 0.0, 1.0);
+
+    assert_eq!(red.delta_e_ok(&red), 0.0);
+    assert_eq!(red.delta_e_76(&red), 0.0);
+
+    assert!((black.delta_e_ok(&white) - 1.0).abs() < 1e-3);
+    assert!((white.delta_e_ok(&black) - 1.0).abs() < 1e-3);
+    assert!((black.delta_e_76(&white) - 100.0).abs() < 1e-2);
+
+    // Alpha is ignored
+    let red_transparent = Color::new(1.0, 0.0, 0.0, 0.0);
+    assert_eq!(red.delta_e_ok(&red_transparent), 0.0);
+    assert_eq!(red.delta_e_76(&red_transparent), 0.0);
+}
+
+#[test]
+fn temperature() {
+    assert_eq!(
+        Color::from_temperature(6600.0).to_rgba8(),
+        [255, 255, 255, 255]
+    );
+
+    // Warm light is reddish, cool light is bluish
+    let [r, _, b, _] = Color::from_temperature(2000.0).to_rgba8();
+    assert!(r > b);
+    let [r, _, b, _] = Color::from_temperature(10000.0).to_rgba8();
+    assert!(r < b);
+
+    // Out of range temperatures are clamped
+    assert_eq!(
+        Color::from_temperature(0.0),
+        Color::from_temperature(1000.0)
+    );
+    assert_eq!(
+        Color::from_temperature(100000.0),
+        Color::from_temperature(40000.0)
+    );
+
+    for kelvin in [1000.0, 2700.0, 6500.0, 40000.0] {
+        let c = Color::from_temperature(kelvin);
+        assert_eq!(Color::from_kelvin_brightness(kelvin, 1.0), c);
+        assert_eq!(Color::from_kelvin_brightness(kelvin, 2.0), c);
+        assert_eq!(
+            Color::from_kelvin_brightness(kelvin, 0.0).to_rgba8(),
+            [0, 0, 0, 255]
+        );
+        assert_eq!(
+            Color::from_kelvin_brightness(kelvin, -1.0).to_rgba8(),
+            [0, 0, 0, 255]
+        );
+    }
+
+    let c = Color::from_kelvin_brightness(6600.0, 0.5);
+    assert_eq!(c.to_rgba8(), [128, 128, 128, 255]);
 }
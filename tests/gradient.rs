@@ -0,0 +1,100 @@
+use csscolorparser::{parse_gradient_stops, ParseColorError};
+
+fn stops(s: &str) -> Vec<(String, Option<f32>)> {
+    parse_gradient_stops(s)
+        .unwrap()
+        .iter()
+        .map(|(c, pos)| (c.to_hex_string(), *pos))
+        .collect()
+}
+
+#[test]
+fn gradient_stops() {
+    assert_eq!(
+        stops("linear-gradient(#f00, #00f)"),
+        [("#ff0000".into(), None), ("#0000ff".into(), None)]
+    );
+
+    assert_eq!(
+        stops(
+            "linear-gradient(to right, rgb(255, 0, 0) 0%, hsl(120 100% 50% / 50%) 50%, #00f 100%)"
+        ),
+        [
+            ("#ff0000".into(), Some(0.0)),
+            ("#00ff0080".into(), Some(0.5)),
+            ("#0000ff".into(), Some(1.0)),
+        ]
+    );
+
+    assert_eq!(
+        stops("Linear-Gradient(45deg, #f00 10% 30%, #0f0)"),
+        [
+            ("#ff0000".into(), Some(0.1)),
+            ("#ff0000".into(), Some(0.3)),
+            ("#00ff00".into(), None),
+        ]
+    );
+
+    assert_eq!(
+        stops("radial-gradient(circle at center, #fff 0%, 25%, #000 20px)"),
+        [("#ffffff".into(), Some(0.0)), ("#000000".into(), None)]
+    );
+
+    assert_eq!(
+        stops("repeating-linear-gradient(-45deg, #0000 0 10%, rgb(0 0 0 / 0.5) 10% 20%)"),
+        [
+            ("#00000000".into(), None),
+            ("#00000000".into(), Some(0.1)),
+            ("#00000080".into(), Some(0.1)),
+            ("#00000080".into(), Some(0.2)),
+        ]
+    );
+
+    // argument list only
+    assert_eq!(
+        stops("#f00 0%, #00f 100%"),
+        [("#ff0000".into(), Some(0.0)), ("#0000ff".into(), Some(1.0))]
+    );
+
+    #[cfg(feature = "named-colors")]
+    assert_eq!(
+        stops("linear-gradient(to bottom right, gold, rebeccapurple 75.5%)"),
+        [("#ffd700".into(), None), ("#663399".into(), Some(0.755))]
+    );
+}
+
+#[test]
+fn invalid_gradient() {
+    let test_data = [
+        ("linear-gradient(#f00, #00g)", ParseColorError::InvalidHex),
+        (
+            "linear-gradient(#ff00zz, #00f)",
+            ParseColorError::InvalidHex,
+        ),
+        ("#ff00zz, #00f", ParseColorError::InvalidHex),
+        (
+            "radial-gradient(rgb(0 0), #00f)",
+            ParseColorError::InvalidRgb,
+        ),
+        (
+            "linear-gradient(#f00, , #00f)",
+            ParseColorError::InvalidUnknown,
+        ),
+        (
+            "linear-gradient(#f00 10% 20% 30%, #00f)",
+            ParseColorError::InvalidUnknown,
+        ),
+        (
+            "linear-gradient(#f00 x, #00f)",
+            ParseColorError::InvalidUnknown,
+        ),
+        (
+            "linear-gradient(#f00, rgb(0 0))",
+            ParseColorError::InvalidRgb,
+        ),
+    ];
+
+    for (s, err) in test_data {
+        assert_eq!(parse_gradient_stops(s).unwrap_err(), err, "{}", s);
+    }
+}
@@ -63,3 +63,26 @@ fn named_colors() {
         assert!(c.name().is_none());
     }
 }
+
+#[test]
+fn from_named() {
+    for (&name, &[r, g, b]) in csscolorparser::NAMED_COLORS.entries() {
+        let c = Color::from_named(name).unwrap();
+        assert_eq!(c.to_rgba8(), [r, g, b, 255]);
+    }
+
+    let test_data = [
+        ("RED", [255, 0, 0, 255]),
+        (" Lime ", [0, 255, 0, 255]),
+        ("transparent", [0, 0, 0, 0]),
+        ("TRANSPARENT", [0, 0, 0, 0]),
+    ];
+    for (s, rgba) in test_data {
+        assert_eq!(Color::from_named(s).unwrap().to_rgba8(), rgba, "{}", s);
+    }
+
+    let test_data = ["", "#f00", "0xff0000", "f00", "rgb(255,0,0)", "redd"];
+    for s in test_data {
+        assert_eq!(Color::from_named(s), None, "{}", s);
+    }
+}
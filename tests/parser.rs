@@ -6,6 +6,8 @@ fn parser() {
         ("transparent", [0, 0, 0, 0]),
         ("#ff00ff64", [255, 0, 255, 100]),
         ("ff00ff64", [255, 0, 255, 100]),
+        ("0xff00ff64", [255, 0, 255, 100]),
+        ("0XFF00FF", [255, 0, 255, 255]),
         ("rgb(247,179,99)", [247, 179, 99, 255]),
         ("rgb(50% 50% 50%)", [128, 128, 128, 255]),
         ("rgb(247,179,99,0.37)", [247, 179, 99, 94]),
@@ -55,8 +57,10 @@ fn equal() {
         ("hsl(180,50%,27%)", "hsl(180,0.5,0.27)"),
         ("rgb(255, 165, 0)", "hsl(38.824 100% 50%)"),
         ("#7654CD", "rgb(46.27% 32.94% 80.39%)"),
-        //#[cfg(feature = "lab")]
-        //("#7654CD", "lab(44.36% 36.05 -58.99)"),
+        #[cfg(feature = "lab")]
+        ("#7654CD", "lab(44.36% 36.05 -58.99)"),
+        #[cfg(feature = "lab")]
+        ("#7654CD", "lab(44.36% 28.84% -47.192%)"),
     ];
 
     for (a, b) in test_data {
@@ -103,6 +107,10 @@ fn red() {
         "f00f",
         "ff0000",
         "ff0000ff",
+        "0xf00",
+        "0xff0000",
+        "0xff0000ff",
+        "0XFF0000",
         "rgb(255,0,0)",
         "rgb(255 0 0)",
         "rgb(700, -99, 0)", // clamp to 0..255
@@ -226,6 +234,10 @@ fn invalid_format() {
         "oklab(0,0,x,0)",
         "oklch(0,0,0,0,0)",
         "oklch(0,0,0,x)",
+        "rgb(nan 0 0)",
+        "hsl(nan 100% 50%)",
+        "hwb(120 inf% 0%)",
+        "oklab(0.5 infinity 0)",
     ];
 
     for s in test_data {
@@ -236,6 +248,8 @@ fn invalid_format() {
     #[rustfmt::skip]
     let test_data = [
         ("#78afzd",          "invalid hex format"),
+        ("0xff0000f",        "invalid hex format"),
+        ("0x",               "invalid hex format"),
         ("rgb(255,0)",       "invalid rgb format"),
         ("hsl(0,100%,2o%)",  "invalid hsl format"),
         ("hsv(360)",         "invalid hsv format"),
@@ -257,3 +271,1208 @@ fn invalid_format() {
         assert_eq!(c.unwrap_err().to_string(), err_msg);
     }
 }
+
+#[test]
+fn too_many_components() {
+    use csscolorparser::ParseColorError;
+
+    let test_data = [
+        ("rgb(1 2 3 4 5)", ParseColorError::InvalidRgb),
+        ("rgba(1, 2, 3, 0.5, 1)", ParseColorError::InvalidRgb),
+        ("rgb(from #f00 r g b extra)", ParseColorError::InvalidRgb),
+        ("hsl(0 100% 50% / 1 1)", ParseColorError::InvalidHsl),
+        ("hwb(0 0% 0% 1 1)", ParseColorError::InvalidHwb),
+        ("hsv(0 100% 100% 1 1)", ParseColorError::InvalidHsv),
+        ("oklab(0.5 0 0 1 1)", ParseColorError::InvalidOklab),
+        ("oklch(0.5 0 0 1 1)", ParseColorError::InvalidOklch),
+    ];
+
+    for (s, err) in test_data {
+        assert_eq!(parse(s).unwrap_err(), err, "{}", s);
+    }
+}
+
+#[test]
+fn surrounding_whitespace() {
+    let test_data = [
+        "\u{00A0}#ff0000\u{00A0}",
+        " \t#ff0000\n",
+        "\u{2003}rgb(255 0 0)\r\n",
+        "\u{00A0}rgb(255,\n0,\t0)\u{00A0}",
+        " hsl(0 100% 50%) ",
+    ];
+    for s in test_data {
+        assert_eq!(parse(s).unwrap().to_rgba8(), [255, 0, 0, 255], "{:?}", s);
+    }
+
+    #[cfg(feature = "named-colors")]
+    assert_eq!(
+        parse("\u{00A0}red\u{00A0}").unwrap().to_rgba8(),
+        [255, 0, 0, 255]
+    );
+
+    // Inside a function, only ASCII whitespace separates arguments
+    for s in ["rgb(255\u{00A0}0 0)", "hsl(0\u{00A0}100%\u{00A0}50%)"] {
+        assert!(parse(s).is_err(), "{:?}", s);
+    }
+}
+
+#[test]
+fn signed_and_decimal_numbers() {
+    let test_data = [
+        ("rgb(+50% .5% -0%)", [128, 1, 0, 255]),
+        ("rgb(+255 -.5 .5)", [255, 0, 1, 255]),
+        ("rgb(+100%, +0%, -.5%, +.5)", [255, 0, 0, 128]),
+        ("rgb(127.5 +127.5 -127.5 / +50%)", [128, 128, 0, 128]),
+        ("hsl(+120 +100% +50%)", [0, 255, 0, 255]),
+        ("hsl(-.5turn 100% 50% / .5)", [0, 255, 255, 128]),
+        ("hwb(+0 +0% -0%)", [255, 0, 0, 255]),
+    ];
+
+    for (s, expected) in test_data {
+        assert_eq!(parse(s).unwrap().to_rgba8(), expected, "{}", s);
+    }
+}
+
+#[test]
+fn rgb_modern_numbers() {
+    let test_data = [
+        ("rgb(255 128 0)", "rgb(255,128,0)", [255, 128, 0, 255]),
+        (
+            "rgb(255.5 128.2 0)",
+            "rgb(255.5,128.2,0)",
+            [255, 128, 0, 255],
+        ),
+        (
+            "rgb(127.5 127.4 127.6)",
+            "rgb(127.5,127.4,127.6)",
+            [128, 127, 128, 255],
+        ),
+        ("rgb(300 0 0)", "rgb(300,0,0)", [255, 0, 0, 255]),
+        ("rgb(1000 256 -20)", "rgb(1000,256,-20)", [255, 255, 0, 255]),
+        ("rgb(0 0 300 / 0.5)", "rgba(0,0,300,0.5)", [0, 0, 255, 128]),
+    ];
+    for (modern, legacy, expected) in test_data {
+        let c = parse(modern).unwrap();
+        assert_eq!(c.to_rgba8(), expected, "{}", modern);
+        assert_eq!(c, parse(legacy).unwrap(), "{}", modern);
+    }
+
+    // Out of range values are clamped, fractions are kept
+    let c = parse("rgb(300 -10 127.5)").unwrap();
+    assert_eq!(c.to_array(), [1.0, 0.0, 0.5, 1.0]);
+}
+
+#[test]
+fn oklab_oklch_signed_numbers() {
+    let test_data = [
+        "oklab(0.628 +0.225 +0.126)",
+        "oklab(+62.8% +56.25% +31.5%)",
+        "oklab(+0.628 0.225 0.126 / +1)",
+        "oklch(+0.628 +0.2577 +29.23)",
+        "oklch(0.628 0.2577 +29.23deg)",
+        "oklch(+62.8% +64.425% +0.0812turn / +100%)",
+    ];
+    let red = parse("oklab(0.628 0.225 0.126)").unwrap();
+    for s in test_data {
+        let c = parse(s).unwrap();
+        assert_eq!(c.to_rgba8(), red.to_rgba8(), "{}", s);
+        assert!(!c.to_css_oklab().contains('+'), "{}", s);
+        assert_eq!(
+            parse(&c.to_css_oklab()).unwrap().to_rgba8(),
+            c.to_rgba8(),
+            "{}",
+            s
+        );
+        assert_eq!(
+            parse(&c.to_css_oklch()).unwrap().to_rgba8(),
+            c.to_rgba8(),
+            "{}",
+            s
+        );
+    }
+}
+
+#[test]
+fn none_alpha() {
+    let test_data = [
+        ("rgb(255 0 0 / none)", [255, 0, 0, 0]),
+        ("rgba(255, 0, 0, none)", [255, 0, 0, 0]),
+        ("hsl(0 100% 50% / none)", [255, 0, 0, 0]),
+        ("hsla(0deg 100% 50% / NONE)", [255, 0, 0, 0]),
+        ("hwb(0 0% 0% / none)", [255, 0, 0, 0]),
+        ("hsv(0 100% 100% / none)", [255, 0, 0, 0]),
+        ("oklab(0.62796 0.22486 0.12585 / none)", [255, 0, 0, 0]),
+        ("oklch(0.62796 0.25768 29.23388 / none)", [255, 0, 0, 0]),
+    ];
+
+    for (s, expected) in test_data {
+        assert_eq!(parse(s).unwrap().to_rgba8(), expected, "{}", s);
+    }
+
+    #[cfg(feature = "lab")]
+    {
+        assert_eq!(
+            parse("lab(0% 0 0 / none)").unwrap().to_rgba8(),
+            [0, 0, 0, 0]
+        );
+        assert_eq!(
+            parse("lch(0% 0 0 / none)").unwrap().to_rgba8(),
+            [0, 0, 0, 0]
+        );
+    }
+}
+
+#[test]
+fn percentage_hue() {
+    use csscolorparser::ParseColorError;
+
+    // Hue can't be a percentage
+    let test_data = [
+        ("hsl(50% 100% 50%)", ParseColorError::InvalidHsl),
+        ("hsla(50%, 100%, 50%, 1)", ParseColorError::InvalidHsl),
+        ("hwb(50% 0% 0%)", ParseColorError::InvalidHwb),
+        ("hsv(50% 100% 100%)", ParseColorError::InvalidHsv),
+        ("oklch(0.5 0.1 50%)", ParseColorError::InvalidOklch),
+    ];
+
+    for (s, err) in test_data {
+        assert_eq!(parse(s).unwrap_err(), err, "{}", s);
+    }
+
+    #[cfg(feature = "lab")]
+    assert_eq!(
+        parse("lch(50% 30 50%)").unwrap_err(),
+        ParseColorError::InvalidLch
+    );
+}
+
+#[test]
+fn none_hue_and_channels() {
+    let test_data = [
+        ("rgb(none 0 0)", [0, 0, 0, 255]),
+        ("rgb(255 none 0)", [255, 0, 0, 255]),
+        ("rgb(100% none none)", [255, 0, 0, 255]),
+        ("hsl(none 0% 50%)", [128, 128, 128, 255]),
+        ("hsl(none 100% 50%)", [255, 0, 0, 255]),
+        ("hwb(none 0% 0%)", [255, 0, 0, 255]),
+        ("hsv(none 0% 100%)", [255, 255, 255, 255]),
+        ("oklch(1 0 none)", [255, 255, 255, 255]),
+        // Any component
+        ("hsl(0 none 50%)", [128, 128, 128, 255]),
+        ("hsl(0 none 0.5)", [128, 128, 128, 255]),
+        ("hsl(0 100% none)", [0, 0, 0, 255]),
+        ("hwb(0 none 0%)", [255, 0, 0, 255]),
+        ("hwb(0 none 0)", [255, 0, 0, 255]),
+        ("hsv(0 100% none)", [0, 0, 0, 255]),
+        ("oklab(none 0 0)", [0, 0, 0, 255]),
+        ("oklab(1 none none)", [255, 255, 255, 255]),
+        ("oklch(none none none)", [0, 0, 0, 255]),
+        ("oklch(1 none 30)", [255, 255, 255, 255]),
+        ("rgb(none none none / none)", [0, 0, 0, 0]),
+        ("color(srgb none 1 none)", [0, 255, 0, 255]),
+    ];
+
+    for (s, expected) in test_data {
+        assert_eq!(parse(s).unwrap().to_rgba8(), expected, "{}", s);
+    }
+
+    // Numbers and percentages still can't be mixed
+    assert!(parse("rgb(none 0% 0)").is_err());
+    assert!(parse("hsl(none 100% 0.5)").is_err());
+
+    let c = parse("oklch(0.5 0.1 none)").unwrap();
+    assert_eq!(c, parse("oklch(0.5 0.1 0)").unwrap());
+
+    #[cfg(feature = "lab")]
+    {
+        assert_eq!(
+            parse("lab(50% none none)").unwrap(),
+            parse("lab(50 0 0)").unwrap()
+        );
+        assert_eq!(
+            parse("lch(none 20 none)").unwrap(),
+            parse("lch(0 20 0)").unwrap()
+        );
+    }
+}
+
+#[test]
+fn none_hue_round_trip() {
+    let c = parse("oklch(0.5 0 none)").unwrap();
+    assert_eq!(c.to_css_oklch(), "oklch(0.5 0 none)");
+    assert_eq!(parse(&c.to_css_oklch()).unwrap().to_rgba8(), c.to_rgba8());
+
+    // The hue doesn't matter when the chroma is 0
+    assert_eq!(parse("oklch(0.5 0 120)").unwrap().to_rgba8(), c.to_rgba8());
+
+    #[cfg(feature = "lab")]
+    {
+        let c = parse("lch(50% 0 none)").unwrap();
+        assert_eq!(c.to_css_lch(), "lch(50 0 none)");
+        assert_eq!(parse(&c.to_css_lch()).unwrap().to_rgba8(), c.to_rgba8());
+        assert_eq!(parse("lch(50% 0 120)").unwrap().to_rgba8(), c.to_rgba8());
+    }
+}
+
+#[test]
+fn parse_colors() {
+    use csscolorparser::parse_colors;
+
+    let s = "#ff0000, rgb(0, 255, 0), hsl(240, 100%, 50%)";
+    let expected = [[255, 0, 0, 255], [0, 255, 0, 255], [0, 0, 255, 255]];
+
+    let colors = parse_colors(s)
+        .map(|c| c.unwrap().to_rgba8())
+        .collect::<Vec<_>>();
+    assert_eq!(colors, expected);
+
+    let colors = parse_colors(s)
+        .rev()
+        .map(|c| c.unwrap().to_rgba8())
+        .collect::<Vec<_>>();
+    assert_eq!(colors, [expected[2], expected[1], expected[0]]);
+
+    let (lower, upper) = parse_colors(s).size_hint();
+    assert!(lower >= 1 && upper >= Some(3));
+
+    // From both ends
+    let mut it = parse_colors(s);
+    assert_eq!(it.next_back().unwrap().unwrap().to_rgba8(), expected[2]);
+    assert_eq!(it.next().unwrap().unwrap().to_rgba8(), expected[0]);
+    assert_eq!(it.next_back().unwrap().unwrap().to_rgba8(), expected[1]);
+    assert!(it.next().is_none());
+    assert!(it.next_back().is_none());
+
+    assert_eq!(parse_colors("").count(), 0);
+    assert_eq!(parse_colors("   ").count(), 0);
+    assert_eq!(parse_colors("#000").count(), 1);
+
+    let res = parse_colors("#000, , rgb(0 0 0)").collect::<Vec<_>>();
+    assert_eq!(res.len(), 3);
+    assert!(res[0].is_ok() && res[1].is_err() && res[2].is_ok());
+}
+
+#[test]
+fn trailing_comma() {
+    use csscolorparser::ParseColorError;
+
+    let test_data = [
+        ("rgb(255, 0, 0,)", [255, 0, 0, 255]),
+        ("rgba(255, 0, 0, 0.5, )", [255, 0, 0, 128]),
+        ("hsl(0,100%,50%,)", [255, 0, 0, 255]),
+        ("hwb(0, 0%, 0%,)", [255, 0, 0, 255]),
+    ];
+    for (s, expected) in test_data {
+        assert_eq!(parse(s).unwrap().to_rgba8(), expected, "{}", s);
+    }
+
+    let test_data = [
+        ("rgb(1,,2)", ParseColorError::InvalidRgb),
+        ("rgb(1,,2,3)", ParseColorError::InvalidRgb),
+        ("rgb(,255,0,0)", ParseColorError::InvalidRgb),
+        ("rgb(255,0,0,,)", ParseColorError::InvalidRgb),
+        ("hsl(0,,100%,50%)", ParseColorError::InvalidHsl),
+        ("oklch(0.5,,0.1,30)", ParseColorError::InvalidOklch),
+        ("foo(1,,2)", ParseColorError::InvalidFunction),
+    ];
+    for (s, err) in test_data {
+        assert_eq!(parse(s).unwrap_err(), err, "{}", s);
+    }
+}
+
+#[test]
+fn hex_fn() {
+    use csscolorparser::hex;
+
+    let test_data = [
+        ("#ff0000", [255, 0, 0, 255]),
+        ("FF0000", [255, 0, 0, 255]),
+        ("0x00ff0080", [0, 255, 0, 128]),
+        (" #00f ", [0, 0, 255, 255]),
+        ("0XFF0000", [255, 0, 0, 255]),
+    ];
+    for (s, expected) in test_data {
+        assert_eq!(hex(s).to_rgba8(), expected, "{}", s);
+        assert_eq!(parse(s).unwrap().to_rgba8(), expected, "{}", s);
+    }
+}
+
+#[test]
+#[should_panic(expected = "invalid hex color")]
+fn hex_fn_invalid() {
+    csscolorparser::hex("red");
+}
+
+#[test]
+fn hsv_format_consistency() {
+    let test_data = [
+        ("hsv(120 1 1)", [0, 255, 0, 255]),
+        ("hsv(120 100% 100%)", [0, 255, 0, 255]),
+        ("hsv(120, 0.5, 1)", [128, 255, 128, 255]),
+        ("hsv(120 1 1 / 50%)", [0, 255, 0, 128]),
+        ("hsv(120 1 1 / 0.5)", [0, 255, 0, 128]),
+        ("hsv(120 100% 100% / 0.5)", [0, 255, 0, 128]),
+        ("hsva(120, 100%, 100%, 50%)", [0, 255, 0, 128]),
+    ];
+    for (s, expected) in test_data {
+        assert_eq!(parse(s).unwrap().to_rgba8(), expected, "{}", s);
+    }
+
+    // Same rule as hsl(): saturation and value can't mix numbers and percentages
+    for s in [
+        "hsv(120 1 100%)",
+        "hsv(120 100% 1)",
+        "hsv(120 100% 1 / 50%)",
+        "hsl(120 1 50%)",
+    ] {
+        assert!(parse(s).is_err(), "{}", s);
+    }
+}
+
+#[test]
+fn css_number_grammar() {
+    let test_data = [
+        // rgb() numbers and percentages
+        ("rgb(.5 5. +5)", [1, 5, 5, 255]),
+        ("rgb(-.5 5e1 2.55e2)", [0, 50, 255, 255]),
+        ("rgb(5e1% .5e2% 1e+2%)", [128, 128, 255, 255]),
+        ("rgb(255 0 0 / 5e-1)", [255, 0, 0, 128]),
+        ("rgb(255 0 0 / 5e1%)", [255, 0, 0, 128]),
+        ("rgb(255 0 0 / .5)", [255, 0, 0, 128]),
+        ("rgb(255 0 0 / 1.)", [255, 0, 0, 255]),
+        ("rgb(255 0 0 / +.5)", [255, 0, 0, 128]),
+        // hue and percentages
+        ("hsl(1.2e2 1e2% 5e1%)", [0, 255, 0, 255]),
+        ("hsl(+120. 100.% 50.%)", [0, 255, 0, 255]),
+        ("hsl(-.5turn 1e2% .5e2%)", [0, 255, 255, 255]),
+        ("hsl(1.2e2deg 100% 50%)", [0, 255, 0, 255]),
+        ("hwb(1.2e2 0. .0)", [0, 255, 0, 255]),
+        ("hsv(1.2e2 1. 1e0)", [0, 255, 0, 255]),
+        // oklab() and oklch()
+        ("oklab(1. 0 -0.)", [255, 255, 255, 255]),
+        ("oklab(1e0 .0 +0)", [255, 255, 255, 255]),
+        ("oklch(1e2% 0. 1.2e2)", [255, 255, 255, 255]),
+        ("oklch(.62796 .25768 29.23388)", [255, 0, 0, 255]),
+    ];
+
+    for (s, expected) in test_data {
+        assert_eq!(parse(s).unwrap().to_rgba8(), expected, "{}", s);
+    }
+
+    let test_data = [
+        "rgb(. 0 0)",
+        "rgb(e1 0 0)",
+        "rgb(5e 0 0)",
+        "rgb(++5 0 0)",
+        "rgb(+-5 0 0)",
+        "rgb(5.5.5 0 0)",
+        "rgb(0x10 0 0)",
+        "rgb(1_0 0 0)",
+        "hsl(. 100% 50%)",
+        "hsl(120 .% 50%)",
+    ];
+
+    for s in test_data {
+        assert!(parse(s).is_err(), "{}", s);
+    }
+}
+
+#[test]
+fn calc() {
+    let test_data = [
+        ("rgb(calc(255) 0 0)", [255, 0, 0, 255]),
+        ("rgb(calc(255 / 2) 0 0)", [128, 0, 0, 255]),
+        ("rgb(calc(1 + 2 * 3) calc(10 - 4 / 2) 0)", [7, 8, 0, 255]),
+        ("rgb(calc((1 + 2) * 3), 0, 0, calc(1 / 2))", [9, 0, 0, 128]),
+        (
+            "rgb(0 0 calc(100 + 50 * 3 + 5) / calc(0.25 * 2))",
+            [0, 0, 255, 128],
+        ),
+        ("hsl(calc(60 * 2) 100% 50%)", [0, 255, 0, 255]),
+        ("hsl(calc(360 - 240 / 2 * 2) 100% 50%)", [0, 255, 0, 255]),
+        ("hwb(calc(100 + 20) 0% 0%)", [0, 255, 0, 255]),
+        ("oklch(calc(1 - 0.5 * 2 + 1) 0 0)", [255, 255, 255, 255]),
+    ];
+    for (s, expected) in test_data {
+        assert_eq!(parse(s).unwrap().to_rgba8(), expected, "{}", s);
+    }
+
+    let test_data = [
+        "rgb(calc(1 +) 0 0)",
+        "rgb(calc(1 2) 0 0)",
+        "rgb(calc(255 / 0) 0 0)",
+        "rgb(calc(r) 0 0)",
+        "rgb(calc((1 + 2) 0 0)",
+        "rgb(calc(1) 0)",
+    ];
+    for s in test_data {
+        assert!(parse(s).is_err(), "{}", s);
+    }
+
+    // Deeply nested input is rejected instead of overflowing the stack
+    let test_data = [
+        format!("rgb(calc({}1) 0 0)", "-".repeat(100_000)),
+        format!(
+            "rgb(calc({}1{}) 0 0)",
+            "(".repeat(100_000),
+            ")".repeat(100_000)
+        ),
+        format!(
+            "rgb(calc({}1{}) 0 0)",
+            "min(".repeat(10_000),
+            ")".repeat(10_000)
+        ),
+        format!("rgb(from red calc({}r) g b)", "+".repeat(100_000)),
+    ];
+    for s in &test_data {
+        assert!(parse(s).is_err());
+    }
+}
+
+#[test]
+fn calc_alpha() {
+    use csscolorparser::ParseColorError;
+
+    let test_data = [
+        ("rgb(255 0 0 / calc(1/2))", 0.5),
+        ("rgb(255 0 0 / calc(1 - 0.25))", 0.75),
+        ("rgba(255, 0, 0, calc(50% / 2))", 0.25),
+        ("hsl(0 100% 50% / calc(min(1, 0.5)))", 0.5),
+        ("oklab(0.5 0 0 / calc(20% + 0.3))", 0.5),
+    ];
+    for (s, expected) in test_data {
+        assert_eq!(parse(s).unwrap().a, expected, "{}", s);
+    }
+
+    let test_data = [
+        ("rgb(255 0 0 / calc(1 +))", ParseColorError::InvalidRgb),
+        ("rgb(255 0 0 / calc(alpha))", ParseColorError::InvalidRgb),
+        ("rgb(255 0 0 / calc(1 / 0))", ParseColorError::InvalidRgb),
+        ("hsl(0 100% 50% / calc(x))", ParseColorError::InvalidHsl),
+    ];
+    for (s, err) in test_data {
+        assert_eq!(parse(s).unwrap_err(), err, "{}", s);
+    }
+}
+
+#[test]
+fn relative_color() {
+    let test_data = [
+        ("rgb(from #bad455 r g b)", [186, 212, 85, 255]),
+        ("rgb(from #bad455 r g b / alpha)", [186, 212, 85, 255]),
+        ("rgb(from #bad455 b g r)", [85, 212, 186, 255]),
+        ("rgb(from #bad455 255 g 0)", [255, 212, 0, 255]),
+        ("rgb(from #bad455 r g b / 0.5)", [186, 212, 85, 128]),
+        ("rgb(from #bad45580 r g b)", [186, 212, 85, 128]),
+        (
+            "rgb(from #bad455 calc(r + 10) calc(g - 12) calc(b * 2))",
+            [196, 200, 170, 255],
+        ),
+        ("rgb(from rgb(255 0 0) r g b)", [255, 0, 0, 255]),
+        (
+            "rgb(from rgb(from #bad455 b g r) b g r)",
+            [186, 212, 85, 255],
+        ),
+        ("rgb(from #bad455 none none none)", [0, 0, 0, 255]),
+        ("hsl(from #ff0000 calc(h + 120) s l)", [0, 255, 0, 255]),
+        ("hsl(from #ff0000 h s calc(l / 2))", [128, 0, 0, 255]),
+        ("hsl(from #ff0000 0.5turn s l)", [0, 255, 255, 255]),
+        ("hwb(from #ff0000 h w b / 50%)", [255, 0, 0, 128]),
+        ("hsv(from #ff0000 h s calc(v / 2))", [128, 0, 0, 255]),
+        ("oklab(from #bad455 l a b)", [186, 212, 85, 255]),
+        ("oklch(from #bad455 l c h)", [186, 212, 85, 255]),
+        ("oklch(from #bad455 l 0 h)", [198, 198, 198, 255]),
+    ];
+    for (s, expected) in test_data {
+        assert_eq!(parse(s).unwrap().to_rgba8(), expected, "{}", s);
+    }
+
+    #[cfg(feature = "named-colors")]
+    assert_eq!(
+        parse("rgb(from red r g calc(b + 255))").unwrap().to_rgba8(),
+        [255, 0, 255, 255]
+    );
+
+    #[cfg(feature = "lab")]
+    {
+        assert_eq!(
+            parse("lab(from #bad455 l a b)").unwrap().to_rgba8(),
+            [186, 212, 85, 255]
+        );
+        assert_eq!(
+            parse("lch(from #bad455 l c h)").unwrap().to_rgba8(),
+            [186, 212, 85, 255]
+        );
+    }
+
+    let test_data = [
+        "rgb(from #bad455 r g)",
+        "rgb(from #bad455 r g b alpha x)",
+        "rgb(from #bad455 r g x)",
+        "rgb(from #bad455 r g h)",
+        "rgb(from #xyz r g b)",
+        "rgb(from r g b)",
+        "hsl(from #bad455 h s calc(l +))",
+        "foo(from #bad455 r g b)",
+    ];
+    for s in test_data {
+        assert!(parse(s).is_err(), "{}", s);
+    }
+}
+
+#[test]
+fn calc_angle_units() {
+    let test_data = [
+        (
+            "hsl(from #bad455 calc(h + 0.5turn) s l)",
+            "hsl(from #bad455 calc(h + 180) s l)",
+        ),
+        (
+            "hwb(from #bad455 calc(h - 100grad) w b)",
+            "hwb(from #bad455 calc(h - 90) w b)",
+        ),
+        (
+            "oklch(from #bad455 l c calc(h + 1rad))",
+            "oklch(from #bad455 l c calc(h + 57.29578))",
+        ),
+        (
+            "hsl(from #bad455 calc(h + 30deg) s l)",
+            "hsl(from #bad455 calc(h + 30) s l)",
+        ),
+        ("hsl(calc(0.25turn + 30deg) 100% 50%)", "hsl(120 100% 50%)"),
+        ("hwb(calc(1turn / 3) 0% 0%)", "hwb(120 0% 0%)"),
+    ];
+    for (a, b) in test_data {
+        assert_eq!(
+            parse(a).unwrap().to_rgba8(),
+            parse(b).unwrap().to_rgba8(),
+            "{}",
+            a
+        );
+    }
+
+    // Angles are only valid for the hue
+    let test_data = [
+        "hsl(from #bad455 h calc(s + 1turn) l)",
+        "rgb(from #bad455 calc(r + 1deg) g b)",
+        "rgb(calc(1deg) 0 0)",
+        "hsl(calc(1foo) 100% 50%)",
+    ];
+    for s in test_data {
+        assert!(parse(s).is_err(), "{}", s);
+    }
+}
+
+#[test]
+fn relative_color_calc_functions() {
+    let test_data = [
+        (
+            "oklch(from #bad455 calc(clamp(0, l, 1)) c h)",
+            [186, 212, 85, 255],
+        ),
+        (
+            "oklch(from #bad455 calc(clamp(0, l * 10, 1)) 0 h)",
+            [255, 255, 255, 255],
+        ),
+        (
+            "rgb(from #bad455 calc(min(r, 100)) calc(max(g, 250)) b)",
+            [100, 250, 85, 255],
+        ),
+        (
+            "rgb(from #bad455 calc(-(r - 255)) calc(-(g) + 255) b)",
+            [69, 43, 85, 255],
+        ),
+        ("hsl(from #ff0000 calc(-(h + 120)) s l)", [0, 0, 255, 255]),
+        (
+            "rgb(from #bad455 r g b / calc(clamp(0, alpha / 2, 1)))",
+            [186, 212, 85, 128],
+        ),
+    ];
+    for (s, expected) in test_data {
+        assert_eq!(parse(s).unwrap().to_rgba8(), expected, "{}", s);
+    }
+}
+
+#[test]
+fn relative_color_percentage() {
+    // Percentages resolve against the channel's reference range
+    let test_data = [
+        ("rgb(from #bad455 50% g b)", [128, 212, 85, 255]),
+        (
+            "rgb(from #bad455 calc(r - 10%) g calc(50% + 0))",
+            [161, 212, 128, 255],
+        ),
+        (
+            "rgb(from #bad455 calc(r * 50% / 100%) g b)",
+            [93, 212, 85, 255],
+        ),
+        (
+            "rgb(from #bad455 calc(100% - r) calc(100% - g) calc(100% - b))",
+            [69, 43, 170, 255],
+        ),
+        ("hsl(from #ff0000 h 50% l)", [191, 64, 64, 255]),
+        ("hsl(from #ff0000 h s calc(l - 10%))", [204, 0, 0, 255]),
+        (
+            "hsl(from #ff0000 h calc(s - 50%) calc(l + 25%))",
+            [223, 159, 159, 255],
+        ),
+        ("hwb(from #ff0000 h calc(w + 20%) b)", [255, 51, 51, 255]),
+        ("oklch(from #bad455 l calc(0%) h)", [198, 198, 198, 255]),
+        (
+            "rgb(from #bad455 r g b / calc(alpha - 25%))",
+            [186, 212, 85, 191],
+        ),
+    ];
+    for (s, expected) in test_data {
+        assert_eq!(parse(s).unwrap().to_rgba8(), expected, "{}", s);
+    }
+
+    // Not for the hue
+    assert!(parse("hsl(from #ff0000 calc(h + 10%) s l)").is_err());
+    assert!(parse("hsl(from #ff0000 10% s l)").is_err());
+}
+
+#[test]
+fn relative_color_alpha() {
+    let c = parse("rgb(from #bad455 r g b / 50%)").unwrap();
+    assert_eq!(c.a, 0.5);
+    assert_eq!(c.to_rgba8(), [186, 212, 85, 128]);
+
+    let test_data = [
+        ("rgb(from #bad455 r g b / 0.5)", 0.5),
+        ("rgb(from #bad455 r g b / 25%)", 0.25),
+        ("rgb(from #bad45580 r g b / 100%)", 1.0),
+        ("rgb(from #bad455 r g b / 200%)", 1.0),
+        ("rgb(from #bad455 r g b / -50%)", 0.0),
+        ("hsl(from #bad455 h s l / 50%)", 0.5),
+        ("hwb(from #bad455 h w b / 50%)", 0.5),
+        ("oklab(from #bad455 l a b / 50%)", 0.5),
+        ("oklch(from #bad455 l c h / 50%)", 0.5),
+        ("oklch(from #bad455 l c h / none)", 0.0),
+    ];
+    for (s, alpha) in test_data {
+        assert_eq!(parse(s).unwrap().a, alpha, "{}", s);
+    }
+}
+
+#[test]
+fn relative_color_function() {
+    let test_data = [
+        ("color(from #ff0000 srgb r g b)", [255, 0, 0, 255]),
+        ("color(from #ff0000 srgb b r g)", [0, 255, 0, 255]),
+        ("color(from #ff0000 srgb g b r)", [0, 0, 255, 255]),
+        ("color(from #bad455 srgb r g b)", [186, 212, 85, 255]),
+        ("color(from #bad455 srgb b g r)", [85, 212, 186, 255]),
+        ("color(from #bad455 srgb r g b / 50%)", [186, 212, 85, 128]),
+        (
+            "color(from #bad455 srgb 1 g calc(b * 2))",
+            [255, 212, 170, 255],
+        ),
+        ("color(from #bad455 srgb 50% g b)", [128, 212, 85, 255]),
+        ("color(from #bad455 srgb-linear r g b)", [186, 212, 85, 255]),
+        (
+            "color(from #bad455 srgb-linear calc(r / 2) g b)",
+            [136, 212, 85, 255],
+        ),
+        (
+            "color(from #000 srgb-linear 0.5 0.5 0.5)",
+            [188, 188, 188, 255],
+        ),
+        ("color(from #bad455 display-p3 r g b)", [186, 212, 85, 255]),
+        ("color(from #ff0000 display-p3 r g b)", [255, 0, 0, 255]),
+        ("color(from #bad455 xyz x y z)", [186, 212, 85, 255]),
+        (
+            "color(from #bad455 xyz-d65 x y z / 50%)",
+            [186, 212, 85, 128],
+        ),
+        ("color(from #bad455 xyz-d50 x y z)", [186, 212, 85, 255]),
+        (
+            "color(from #000 xyz-d50 0.9642 1 0.8251)",
+            [255, 255, 255, 255],
+        ),
+    ];
+    for (s, expected) in test_data {
+        assert_eq!(parse(s).unwrap().to_rgba8(), expected, "{}", s);
+    }
+
+    #[cfg(feature = "named-colors")]
+    assert_eq!(
+        parse("color(from red srgb r g b)").unwrap().to_rgba8(),
+        [255, 0, 0, 255]
+    );
+
+    // Same as the absolute color()
+    for space in ["display-p3", "xyz-d65", "xyz-d50"] {
+        let relative = parse(&format!("color(from #000 {} 0.2 0.4 0.6)", space)).unwrap();
+        let absolute = parse(&format!("color({} 0.2 0.4 0.6)", space)).unwrap();
+        assert_eq!(relative.to_rgba16(), absolute.to_rgba16(), "{}", space);
+    }
+
+    // Out of range results are clamped, like the absolute color()
+    let test_data = [
+        "color(from #ff0000 srgb calc(r + 1) g b)",
+        "color(from #ff0000 srgb-linear calc(r * 3) g b)",
+        "color(from #ff0000 display-p3 calc(r * 2) g b)",
+        "color(from #ff0000 xyz calc(x * 3) y z)",
+        "color(srgb 2 0 0)",
+    ];
+    for s in test_data {
+        let c = parse(s).unwrap();
+        for t in [c.r, c.g, c.b, c.a] {
+            assert!((0.0..=1.0).contains(&t), "{}", s);
+        }
+    }
+    assert_eq!(
+        parse("color(from #ff0000 srgb calc(r + 1) -1 b)").unwrap(),
+        Color::new(1.0, 0.0, 0.0, 1.0)
+    );
+
+    let test_data = [
+        "color(from #bad455 r g b)",
+        "color(from #bad455 rgb r g b)",
+        "color(from #bad455 display-p3 x y z)",
+        "color(from #bad455 xyz r g b)",
+        "color(from #bad455 srgb h s l)",
+        "color(from #bad455 foo r g b)",
+        "color(from #bad455)",
+        "srgb(from #bad455 r g b)",
+    ];
+    for s in test_data {
+        assert!(parse(s).is_err(), "{}", s);
+    }
+}
+
+#[test]
+fn color_function() {
+    use csscolorparser::ParseColorError;
+
+    let test_data = [
+        ("color(srgb 1 0.5 0)", [255, 128, 0, 255]),
+        ("color(srgb 100% 50% 0%)", [255, 128, 0, 255]),
+        ("color(srgb 1 0.5 0 / 50%)", [255, 128, 0, 128]),
+        ("color(srgb 1 0.5 0 / 0.5)", [255, 128, 0, 128]),
+        ("COLOR(SRGB 1 0.5 0)", [255, 128, 0, 255]),
+        ("color(srgb none 1 calc(50% / 2))", [0, 255, 64, 255]),
+        ("color(srgb-linear 0 0 0)", [0, 0, 0, 255]),
+        ("color(srgb-linear 0.5 0.5 0.5)", [188, 188, 188, 255]),
+        ("color(srgb-linear 100% 0% 0%)", [255, 0, 0, 255]),
+        ("color(display-p3 0.5 0.5 0.5)", [128, 128, 128, 255]),
+        ("color(display-p3 0.8 0.3 0.2)", [221, 64, 37, 255]),
+        ("color(display-p3 1 1 1 / 25%)", [255, 255, 255, 64]),
+        ("color(xyz 0.9505 1 1.089)", [255, 255, 255, 255]),
+        ("color(xyz-d65 0.4124 0.2126 0.0193)", [255, 0, 0, 255]),
+        ("color(xyz-d50 0.9643 1 0.8251)", [255, 255, 255, 255]),
+        ("color(xyz 0.9505 1 1.089 / 50%)", [255, 255, 255, 128]),
+        ("color(xyz-d50 0.9643 1 0.8251 / 0)", [255, 255, 255, 0]),
+        // Out of the sRGB gamut, clamped
+        ("color(display-p3 1 0 0)", [255, 0, 0, 255]),
+        ("color(display-p3 1 0.5 0)", [255, 118, 0, 255]),
+        ("color(srgb 2 -1 0.5 / 150%)", [255, 0, 128, 255]),
+    ];
+    for (s, expected) in test_data {
+        assert_eq!(parse(s).unwrap().to_rgba8(), expected, "{}", s);
+    }
+
+    let c = parse("color(display-p3 1 0 0)").unwrap();
+    assert!([c.r, c.g, c.b].iter().all(|t| (0.0..=1.0).contains(t)));
+
+    let test_data = [
+        "color(rec2020 1 0 0)",
+        "color(foo 1 0 0)",
+        "color(1 0 0)",
+        "color(srgb 1 0)",
+        "color(srgb 1 0 0 0 0)",
+        "color(srgb a 0 0)",
+        "color(srgb 1 0 0 / x)",
+        "color(srgb 1deg 0 0)",
+        "color()",
+    ];
+    for s in test_data {
+        assert_eq!(
+            parse(s).unwrap_err(),
+            ParseColorError::InvalidColorFunction,
+            "{}",
+            s
+        );
+    }
+    assert_eq!(
+        parse("color(from #bad455 foo r g b)").unwrap_err(),
+        ParseColorError::InvalidColorFunction
+    );
+
+    // Strict parsing needs the slash before the alpha
+    use csscolorparser::parse_strict;
+    assert!(parse_strict("color(srgb 1 0.5 0 / 0.5)").is_ok());
+    assert!(parse_strict("color(srgb 1 0.5 0)").is_ok());
+    assert!(parse_strict("color(srgb 1 0.5 0 0.5)").is_err());
+}
+
+#[test]
+fn relative_color_alias_names() {
+    use csscolorparser::ParseColorError;
+
+    let test_data = [
+        ("hsl", "hsla", "h s l"),
+        ("hwb", "hwba", "h w b"),
+        ("hsv", "hsva", "h s v"),
+        ("rgb", "rgba", "r g b"),
+    ];
+    for (name, alias, channels) in test_data {
+        for rest in [
+            channels.to_string(),
+            format!("{} / alpha", channels),
+            format!("{} / 50%", channels),
+        ] {
+            let s1 = format!("{}(from #bad455 {})", name, rest);
+            let s2 = format!("{}(from #bad455 {})", alias, rest);
+            assert_eq!(parse(&s1).unwrap(), parse(&s2).unwrap(), "{}", s2);
+        }
+    }
+
+    let test_data = [
+        ("hsva(from #bad455 h s v / alpha)", [186, 212, 85, 255]),
+        ("hwba(from #bad455 h w b / alpha)", [186, 212, 85, 255]),
+        (
+            "hsva(from #bad455 calc(h + 180) s v / 50%)",
+            [111, 85, 212, 128],
+        ),
+        ("hwba(from #bad455 h w b / 0.5)", [186, 212, 85, 128]),
+    ];
+    for (s, expected) in test_data {
+        assert_eq!(parse(s).unwrap().to_rgba8(), expected, "{}", s);
+    }
+
+    let test_data = [
+        ("hsva(from #bad455 h x v)", ParseColorError::InvalidHsv),
+        ("hsva(from #bad455 h s)", ParseColorError::InvalidHsv),
+        ("hwba(from #bad455 h x b)", ParseColorError::InvalidHwb),
+        ("hwba(from #bad455 h w b / x)", ParseColorError::InvalidHwb),
+        ("hsla(from #bad455 h s v)", ParseColorError::InvalidHsl),
+    ];
+    for (s, err) in test_data {
+        assert_eq!(parse(s).unwrap_err(), err, "{}", s);
+    }
+}
+
+#[test]
+fn x11() {
+    use csscolorparser::{parse_x11, ParseColorError};
+
+    let test_data = [
+        ("rgb:ff/00/00", [255, 0, 0, 255]),
+        ("rgb:FF/80/00", [255, 128, 0, 255]),
+        ("rgb:f/8/0", [255, 136, 0, 255]),
+        ("rgb:fff/800/000", [255, 128, 0, 255]),
+        ("rgb:ffff/8080/0000", [255, 128, 0, 255]),
+        ("rgb:f/80/0000", [255, 128, 0, 255]),
+        (" RGB:00/ff/00 ", [0, 255, 0, 255]),
+        ("rgbi:1/0/0", [255, 0, 0, 255]),
+        ("rgbi:1/0.5/0", [255, 128, 0, 255]),
+        ("rgbi:0/.25/1e0", [0, 64, 255, 255]),
+    ];
+    for (s, expected) in test_data {
+        assert_eq!(parse_x11(s).unwrap().to_rgba8(), expected, "{}", s);
+    }
+
+    let test_data = [
+        ("rgb:ff/00", ParseColorError::InvalidHex),
+        ("rgb:ff/00/00/00", ParseColorError::InvalidHex),
+        ("rgb:ff//00", ParseColorError::InvalidHex),
+        ("rgb:fffff/0/0", ParseColorError::InvalidHex),
+        ("rgb:gg/00/00", ParseColorError::InvalidHex),
+        ("rgb:+f/0/0", ParseColorError::InvalidHex),
+        ("rgb:0/-0/0", ParseColorError::InvalidHex),
+        ("rgbi:1/0", ParseColorError::InvalidRgb),
+        ("rgbi:2/0/0", ParseColorError::InvalidRgb),
+        ("rgbi:-0.5/0/0", ParseColorError::InvalidRgb),
+        ("rgbi:x/0/0", ParseColorError::InvalidRgb),
+        ("#ff0000", ParseColorError::InvalidUnknown),
+        ("rgb(255 0 0)", ParseColorError::InvalidUnknown),
+        ("", ParseColorError::InvalidUnknown),
+    ];
+    for (s, err) in test_data {
+        assert_eq!(parse_x11(s).unwrap_err(), err, "{}", s);
+    }
+}
+
+#[test]
+fn named_map() {
+    use csscolorparser::{parse_named_map, ParseColorError, ParseNamedMapError};
+
+    let theme = "
+        primary: #f00;
+        accent : rgb(0 0 255);;
+        muted:hsl(0 0% 50% / 50%) ;
+        primary-dark: oklch(from #f00 calc(l / 2) c h);
+    ";
+    let map = parse_named_map(theme).unwrap();
+    assert_eq!(map.len(), 4);
+    assert_eq!(map["primary"].to_rgba8(), [255, 0, 0, 255]);
+    assert_eq!(map["accent"].to_rgba8(), [0, 0, 255, 255]);
+    assert_eq!(map["muted"].to_rgba8(), [128, 128, 128, 128]);
+    assert!(map.contains_key("primary-dark"));
+
+    // Last one wins
+    let map = parse_named_map("a: #f00; a: #0f0").unwrap();
+    assert_eq!(map["a"].to_rgba8(), [0, 255, 0, 255]);
+
+    assert!(parse_named_map("").unwrap().is_empty());
+    assert!(parse_named_map(" ; ;").unwrap().is_empty());
+
+    let test_data = [
+        ("a: #f00; b: #ggg", "b: #ggg", ParseColorError::InvalidHex),
+        ("a: rgb(0 0)", "a: rgb(0 0)", ParseColorError::InvalidRgb),
+        ("a: #f00; blue", "blue", ParseColorError::InvalidUnknown),
+        (": #f00", ": #f00", ParseColorError::InvalidUnknown),
+        ("a:", "a:", ParseColorError::InvalidUnknown),
+    ];
+    for (s, entry, kind) in test_data {
+        assert_eq!(
+            parse_named_map(s).unwrap_err(),
+            ParseNamedMapError {
+                entry: entry.to_string(),
+                kind
+            },
+            "{}",
+            s
+        );
+    }
+
+    let err = parse_named_map("x: #ggg").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid entry \"x: #ggg\": invalid hex format"
+    );
+}
+
+#[test]
+fn strict_arg_count() {
+    use csscolorparser::{parse_strict, ParseColorError};
+
+    let test_data = [
+        ("rgba(0,0,0,0.5)", [0, 0, 0, 128]),
+        ("rgb(255, 0, 0)", [255, 0, 0, 255]),
+        ("rgb(255, 0, 0,)", [255, 0, 0, 255]),
+        ("hsla(0, 100%, 50%, 50%)", [255, 0, 0, 128]),
+        ("hsl(0, 100%, 50%)", [255, 0, 0, 255]),
+        // The modern syntax allows the alpha for both
+        ("rgb(0 0 0 / 0.5)", [0, 0, 0, 128]),
+        ("rgba(0 0 0)", [0, 0, 0, 255]),
+        ("hsl(0 100% 50% / 50%)", [255, 0, 0, 128]),
+        ("hsla(0 100% 50%)", [255, 0, 0, 255]),
+        // Other functions are unchanged
+        ("hwb(0, 0%, 0%, 0.5)", [255, 0, 0, 128]),
+        ("#f00", [255, 0, 0, 255]),
+    ];
+    for (s, expected) in test_data {
+        assert_eq!(parse_strict(s).unwrap().to_rgba8(), expected, "{}", s);
+    }
+
+    let test_data = [
+        ("rgb(0,0,0,0.5)", ParseColorError::InvalidRgb),
+        ("rgba(0, 0, 0)", ParseColorError::InvalidRgb),
+        ("hsl(0, 100%, 50%, 0.5)", ParseColorError::InvalidHsl),
+        ("hsla(0, 100%, 50%)", ParseColorError::InvalidHsl),
+    ];
+    for (s, err) in test_data {
+        assert_eq!(parse_strict(s).unwrap_err(), err, "{}", s);
+        assert!(parse(s).is_ok(), "{}", s);
+    }
+}
+
+#[test]
+fn strict_mixed_separators() {
+    use csscolorparser::{parse_strict, ParseColorError};
+
+    let test_data = [
+        "rgb(255, 0, 0 / 0.5)",
+        "rgb(255 0, 0)",
+        "rgba(255,0,0/50%)",
+        "rgb(255 0 0 0.5)",
+        "rgb(255 0 / 0 0.5)",
+        "rgb(255 0 0,)",
+        "hsl(0, 100% 50%)",
+        "hsla(0, 100%, 50% / 50%)",
+        "hsl(0 100% 50% 50%)",
+        "hwb(0 0%, 0%)",
+        "hwb(0, 0%, 0% / 0.5)",
+        "hwb(0 0% 0% 0.5)",
+        "color(srgb 1 0 0,)",
+        "rgb(from #f00 r g, b)",
+        "rgb(from #f00 r, g, b)",
+        "rgb(from #f00 r g b 50%)",
+        "hsl(from #f00 h s l,)",
+        "color(from #f00 srgb r g b, 50%)",
+    ];
+    for s in test_data {
+        assert_eq!(
+            parse_strict(s).unwrap_err(),
+            ParseColorError::InvalidSeparators,
+            "{}",
+            s
+        );
+        assert!(parse(s).is_ok(), "{}", s);
+    }
+    assert_eq!(
+        ParseColorError::InvalidSeparators.to_string(),
+        "invalid or mixed argument separators"
+    );
+
+    let test_data = [
+        ("rgb(255 0 0 / 0.5)", [255, 0, 0, 128]),
+        ("rgba(255, 0, 0, 0.5)", [255, 0, 0, 128]),
+        ("hsl(0 100% 50% / 50%)", [255, 0, 0, 128]),
+        ("hwb(0 0% 0% / 0.5)", [255, 0, 0, 128]),
+        ("hwb(0, 0%, 0%)", [255, 0, 0, 255]),
+        ("rgb(calc(510 / 2) 0 0)", [255, 0, 0, 255]),
+        ("rgb(from #f00 r g b / 50%)", [255, 0, 0, 128]),
+        ("hsl(from #f00 calc(h + 120) s l)", [0, 255, 0, 255]),
+        ("color(from #f00 srgb b g r / 0.5)", [0, 0, 255, 128]),
+    ];
+    for (s, expected) in test_data {
+        assert_eq!(parse_strict(s).unwrap().to_rgba8(), expected, "{}", s);
+    }
+}
+
+#[test]
+fn split_css_value() {
+    use csscolorparser::split_css_value;
+
+    let test_data: &[(&str, &[&str])] = &[
+        ("", &[]),
+        ("0 0 2px red", &["0", "0", "2px", "red"]),
+        (
+            "  inset 1px 1px hsl(0 100% 50% / 50%)\n",
+            &["inset", "1px", "1px", "hsl(0 100% 50% / 50%)"],
+        ),
+        (
+            "linear-gradient(red 0%, blue 100%) no-repeat",
+            &["linear-gradient(red 0%, blue 100%)", "no-repeat"],
+        ),
+        ("a b, c", &["a", "b,", "c"]),
+        // A non-breaking space is not CSS whitespace
+        ("1px\u{a0}2px red", &["1px\u{a0}2px", "red"]),
+        ("\u{a0}red", &["\u{a0}red"]),
+    ];
+    for (s, expected) in test_data {
+        assert_eq!(&split_css_value(s).collect::<Vec<_>>(), expected, "{}", s);
+    }
+}
+
+#[test]
+fn argb_hex() {
+    use csscolorparser::{parse_argb_hex, ParseColorError};
+
+    let test_data = [
+        ("#80ff0000", [255, 0, 0, 128]),
+        ("80FF0000", [255, 0, 0, 128]),
+        ("0x80ff0000", [255, 0, 0, 128]),
+        (" #ff00ff00 ", [0, 255, 0, 255]),
+        ("#00000000", [0, 0, 0, 0]),
+        ("#8f00", [255, 0, 0, 136]),
+        // Without alpha, same as CSS
+        ("#ff0000", [255, 0, 0, 255]),
+        ("#0f0", [0, 255, 0, 255]),
+    ];
+    for (s, expected) in test_data {
+        assert_eq!(parse_argb_hex(s).unwrap().to_rgba8(), expected, "{}", s);
+    }
+
+    // Round trip
+    let c = parse_argb_hex("#80ff0000").unwrap();
+    assert_eq!(c.to_argb_hex(), "#80ff0000");
+    assert_eq!(c.to_css_hex(), "#ff000080");
+    assert_eq!(parse(&c.to_css_hex()).unwrap(), c);
+    for s in ["#12345678", "#ffabcdef", "#00ffffff"] {
+        assert_eq!(parse_argb_hex(s).unwrap().to_argb_hex(), s);
+    }
+
+    for s in ["", "#", "#12345", "#1234567", "#80ff00zz", "#80ff00€"] {
+        assert_eq!(
+            parse_argb_hex(s).unwrap_err(),
+            ParseColorError::InvalidHex,
+            "{}",
+            s
+        );
+    }
+}
+
+#[test]
+fn is_valid() {
+    use csscolorparser::is_valid;
+
+    let test_data = [
+        "#f00",
+        "ff0000",
+        "rgb(255 0 0)",
+        "hsl(0, 100%, 50%)",
+        "oklch(0.6 0.2 30 / 50%)",
+        "rgb(from #f00 r g b)",
+        "transparent",
+    ];
+    for s in test_data {
+        assert!(is_valid(s), "{}", s);
+        assert!(Color::is_valid_css(s), "{}", s);
+    }
+
+    let test_data = [
+        "",
+        "#ff000",
+        "rgb(255 0)",
+        "hsl(0 100% 50%",
+        "cmyk(0 0 0 0)",
+    ];
+    for s in test_data {
+        assert!(!is_valid(s), "{}", s);
+        assert!(!Color::is_valid_css(s), "{}", s);
+    }
+
+    #[cfg(feature = "named-colors")]
+    {
+        assert!(is_valid("RebeccaPurple"));
+        assert!(!is_valid("bloodred"));
+    }
+}
+
+#[test]
+fn calc_out_of_range() {
+    let test_data = [
+        ("rgb(calc(255*2) 0 0)", [255, 0, 0, 255]),
+        ("rgb(calc(-255) calc(1e10) 0)", [0, 255, 0, 255]),
+        ("rgb(255 0 0 / calc(5))", [255, 0, 0, 255]),
+        ("rgb(255 0 0 / calc(-1))", [255, 0, 0, 0]),
+        ("hwb(calc(120) 0% 0%)", [0, 255, 0, 255]),
+        ("oklab(calc(2) 0 0)", [255, 255, 255, 255]),
+        ("oklch(calc(-1) 0 30)", [0, 0, 0, 255]),
+    ];
+    for (s, expected) in test_data {
+        assert_eq!(parse(s).unwrap().to_rgba8(), expected, "{}", s);
+    }
+
+    // The lightness is clamped to its range, so the color is in gamut
+    let c = parse("oklab(calc(2) 0 0)").unwrap();
+    assert_eq!(c, parse("oklab(1 0 0)").unwrap());
+    let c = parse("oklch(from #bad455 calc(l * 2) 0 h)").unwrap();
+    assert_eq!(c, parse("oklch(1 0 0)").unwrap());
+    #[cfg(feature = "lab")]
+    {
+        let c = parse("lab(calc(150) 0 0)").unwrap();
+        assert_eq!(c, parse("lab(100 0 0)").unwrap());
+        let c = parse("lch(calc(-1) 0 0)").unwrap();
+        assert_eq!(c, parse("lch(0 0 0)").unwrap());
+    }
+
+    // Non-finite results are rejected
+    let test_data = [
+        "rgb(calc(1/0) 0 0)",
+        "rgb(calc(-1/0) 0 0)",
+        "rgb(calc(0/0) 0 0)",
+        "hsl(calc(1e39 * 1e39) 100% 50%)",
+        "oklch(0.5 0.1 calc(1/0))",
+        "rgb(255 0 0 / calc(1/0))",
+    ];
+    for s in test_data {
+        assert!(parse(s).is_err(), "{}", s);
+    }
+}